@@ -0,0 +1,163 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use crate::{try_parse, Error, Result, Solution};
+
+/// Expected answers, one line per part: `DAY PART ANSWER`, e.g. `13 2 HZKHFEJZ`.
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: HashMap<(u8, u8), String>,
+}
+
+impl FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Answers> {
+        let mut answers = HashMap::new();
+
+        for line in input.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut columns = line.splitn(3, char::is_whitespace);
+            let day = try_parse(columns.next().unwrap_or(line))?;
+            let part = columns.next().ok_or_else(|| Error::parse(line, "a part"))?;
+            let answer = columns
+                .next()
+                .ok_or_else(|| Error::parse(line, "an answer"))?;
+
+            if part != "1" && part != "2" {
+                return Err(Error::parse(part, "part 1 or 2"));
+            }
+
+            answers.insert((day, try_parse(part)?), answer.trim().to_string());
+        }
+
+        Ok(Answers { answers })
+    }
+}
+
+impl Answers {
+    pub fn expected(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Compare both parts of a solution with the expected answers.
+    pub fn check(&self, solution: &Solution) -> [Check; 2] {
+        [1, 2].map(|part| Check::new(solution, part, self.expected(solution.day(), part)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+/// The outcome of comparing one part with its expected answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub expected: Option<String>,
+    pub actual: String,
+}
+
+impl Check {
+    pub fn new(solution: &Solution, part: u8, expected: Option<&str>) -> Check {
+        let actual = match part {
+            1 => solution.part_1(),
+            _ => solution.part_2(),
+        };
+
+        Check {
+            day: solution.day(),
+            part,
+            expected: expected.map(String::from),
+            actual: actual.to_string(),
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        match &self.expected {
+            Some(expected) if *expected == self.actual => Status::Pass,
+            Some(_) => Status::Fail,
+            None => Status::Missing,
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {: >2} part {}: ", self.day, self.part)?;
+
+        match (self.status(), &self.expected) {
+            (Status::Pass, _) => write!(f, "PASS {}", self.actual),
+            (Status::Fail, Some(expected)) => {
+                write!(f, "FAIL expected {}, found {}", expected, self.actual)
+            }
+            _ => write!(f, "MISSING found {}", self.actual),
+        }
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::Timings;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers: Answers = "# day part answer\n1 1 1759\n\n13 2 HZKHFEJZ\n"
+            .parse()
+            .unwrap();
+        assert_eq!(answers.expected(1, 1), Some("1759"));
+        assert_eq!(answers.expected(13, 2), Some("HZKHFEJZ"));
+        assert_eq!(answers.expected(1, 2), None);
+    }
+
+    #[test]
+    fn test_parse_invalid_part() {
+        let input = "1 1 1759\n1 3 1805";
+        let error = input.parse::<Answers>().unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected part 1 or 2, found \"3\""
+        )
+    }
+
+    #[test]
+    fn test_parse_missing_answer() {
+        assert!("1 1".parse::<Answers>().is_err())
+    }
+
+    #[test]
+    fn test_check() {
+        let answers: Answers = "1 1 1759\n".parse().unwrap();
+        let [part_1, part_2] = answers.check(&Solution::new(1, 1760, 1805, Timings::default()));
+
+        assert_eq!(part_1.status(), Status::Fail);
+        assert_eq!(
+            part_1.to_string(),
+            "Day  1 part 1: FAIL expected 1759, found 1760"
+        );
+        assert_eq!(part_2.status(), Status::Missing);
+        assert_eq!(part_2.to_string(), "Day  1 part 2: MISSING found 1805");
+    }
+
+    #[test]
+    fn test_check_pass() {
+        let answers: Answers = "1 1 1759\n1 2 1805".parse().unwrap();
+        let checks = answers.check(&Solution::new(1, 1759, 1805, Timings::default()));
+        assert!(checks.iter().all(|check| check.status() == Status::Pass))
+    }
+}
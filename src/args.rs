@@ -0,0 +1,258 @@
+use advent_of_code::{Format, Part};
+
+/// The last day of the calendar.
+const LAST_DAY: u8 = 25;
+
+pub const USAGE: &str = "\
+Usage: advent-of-code [DAYS] [OPTIONS]
+
+DAYS is a day, a range or a comma separated list of both, e.g. 3, 3-7 or 1,4,20-22.
+All solved days are run when no days are given.
+
+Options:
+  -i, --input PATH           Solve one day with another input file, or - for stdin
+  -P, --part 1|2             Only solve one part
+  -f, --format FORMAT        Print as table (default), json, csv or markdown
+  -c, --check PATH           Compare the answers with an answers file
+  -e, --example              Solve the examples from the puzzle descriptions and check the answers
+  -b, --bench N              Benchmark each day N times
+      --baseline PATH        Compare the benchmark with a saved baseline
+      --save-baseline PATH   Save the benchmark as a baseline
+  -p, --parallel             Solve the days on one thread per core
+  -h, --help                 Show this help";
+
+pub struct Args {
+    /// The selected days in order, or empty for all days.
+    pub days: Vec<u8>,
+    pub part: Option<Part>,
+    pub input: Option<String>,
+    pub format: Format,
+    pub check: Option<String>,
+    pub bench: Option<usize>,
+    pub baseline: Option<String>,
+    pub save_baseline: Option<String>,
+    pub parallel: bool,
+    pub example: bool,
+}
+
+pub enum Command {
+    Run(Args),
+    Help,
+}
+
+/// Parse the command line arguments, without the program name.
+/// The error is a message that should be shown together with the usage.
+pub fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut days = vec![];
+    let mut part = None;
+    let mut input = None;
+    let mut format = None;
+    let mut check = None;
+    let mut bench = None;
+    let mut baseline = None;
+    let mut save_baseline = None;
+    let mut parallel = false;
+    let mut example = false;
+
+    while let Some(arg) = args.next() {
+        let mut value = |what: &str| args.next().ok_or_else(|| format!("{} needs {}", arg, what));
+
+        match arg.as_str() {
+            "--help" | "-h" => return Ok(Command::Help),
+            "--input" | "-i" => input = Some(value("a path or - for stdin")?),
+            "--part" | "-P" => part = Some(parse_value(&value("1 or 2")?)?),
+            "--format" | "-f" => format = Some(parse_value(&value("a format")?)?),
+            "--check" | "-c" => check = Some(value("a path to an answers file")?),
+            "--bench" | "-b" => bench = Some(parse_runs(&value("the number of runs")?)?),
+            "--baseline" => baseline = Some(value("a path")?),
+            "--save-baseline" => save_baseline = Some(value("a path")?),
+            "--parallel" | "-p" => parallel = true,
+            "--example" | "-e" => example = true,
+            option if option.starts_with('-') => return Err(format!("Unknown option {}", option)),
+            selection => days.extend(parse_days(selection)?),
+        }
+    }
+
+    days.sort_unstable();
+    days.dedup();
+
+    if input.is_some() && days.len() != 1 {
+        return Err(String::from("--input needs exactly one day"));
+    }
+    // Options that the selected mode doesn't use are rejected instead of ignored.
+    if example
+        && (input.is_some() || check.is_some() || bench.is_some() || format.is_some() || parallel)
+    {
+        return Err(String::from(
+            "--example can't be combined with --input, --check, --bench, --format or --parallel",
+        ));
+    }
+    if bench.is_some() && (check.is_some() || format.is_some() || parallel) {
+        return Err(String::from(
+            "--bench can't be combined with --check, --format or --parallel",
+        ));
+    }
+    if check.is_some() && format.is_some() {
+        return Err(String::from("--check can't be combined with --format"));
+    }
+    if bench.is_none() && (baseline.is_some() || save_baseline.is_some()) {
+        return Err(String::from("--baseline and --save-baseline need --bench"));
+    }
+
+    Ok(Command::Run(Args {
+        days,
+        part,
+        input,
+        format: format.unwrap_or(Format::Table),
+        check,
+        bench,
+        baseline,
+        save_baseline,
+        parallel,
+        example,
+    }))
+}
+
+fn parse_value<T>(value: &str) -> Result<T, String>
+where
+    T: std::str::FromStr<Err = advent_of_code::Error>,
+{
+    value.parse().map_err(|error| format!("{}", error))
+}
+
+fn parse_runs(runs: &str) -> Result<usize, String> {
+    match runs.parse() {
+        Ok(runs) if runs > 0 => Ok(runs),
+        _ => Err(format!(
+            "expected a positive number of runs, found {:?}",
+            runs
+        )),
+    }
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    match day.parse() {
+        Ok(day) if (1..=LAST_DAY).contains(&day) => Ok(day),
+        _ => Err(format!(
+            "expected a day between 1 and {}, found {:?}",
+            LAST_DAY, day
+        )),
+    }
+}
+
+/// Parse a selection of days like `3`, `3-7` or `1,4,20-22`.
+pub fn parse_days(selection: &str) -> Result<Vec<u8>, String> {
+    let mut days = vec![];
+
+    for part in selection.split(',') {
+        match part.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (parse_day(from)?, parse_day(to)?);
+                if from > to {
+                    return Err(format!("expected an increasing range, found {:?}", part));
+                }
+                days.extend(from..=to);
+            }
+            None => days.push(parse_day(part)?),
+        }
+    }
+
+    Ok(days)
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    fn parse(args: &str) -> Result<Args, String> {
+        match parse_args(args.split_whitespace().map(String::from))? {
+            Command::Run(args) => Ok(args),
+            Command::Help => Err(String::from("help")),
+        }
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("3"), Ok(vec![3]));
+        assert_eq!(parse_days("3-7"), Ok(vec![3, 4, 5, 6, 7]));
+        assert_eq!(parse_days("1,4,20-22"), Ok(vec![1, 4, 20, 21, 22]));
+    }
+
+    #[test]
+    fn test_parse_invalid_days() {
+        assert_eq!(
+            parse_days("0"),
+            Err(String::from("expected a day between 1 and 25, found \"0\""))
+        );
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("1,,2").is_err());
+        assert!(parse_days("7-3").is_err());
+        assert!(parse_days("x").is_err());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = parse("4,1 2-3 --part 2 -f csv").unwrap();
+        assert_eq!(args.days, vec![1, 2, 3, 4]);
+        assert_eq!(args.part, Some(Part::Two));
+        assert_eq!(args.format, Format::Csv);
+        assert!(!args.parallel);
+    }
+
+    #[test]
+    fn test_parse_no_days() {
+        assert!(parse("-p").unwrap().days.is_empty())
+    }
+
+    #[test]
+    fn test_parse_invalid_args() {
+        assert_eq!(
+            parse("--part 3").err().unwrap(),
+            "expected part 1 or 2, found \"3\""
+        );
+        assert_eq!(
+            parse("--input").err().unwrap(),
+            "--input needs a path or - for stdin"
+        );
+        assert_eq!(
+            parse("--input x.txt").err().unwrap(),
+            "--input needs exactly one day"
+        );
+        assert_eq!(
+            parse("--verbose").err().unwrap(),
+            "Unknown option --verbose"
+        );
+        assert!(parse("--bench 0").is_err());
+        assert!(parse("--baseline b.txt").is_err());
+        assert!(parse("1 -e -i x.txt").is_err());
+    }
+
+    #[test]
+    fn test_parse_ignored_options() {
+        assert_eq!(
+            parse("-b 5 -c answers.txt").err().unwrap(),
+            "--bench can't be combined with --check, --format or --parallel"
+        );
+        assert!(parse("-b 5 -f json").is_err());
+        assert!(parse("-b 5 -p").is_err());
+        assert!(parse("-e -f csv").is_err());
+        assert!(parse("-e -p").is_err());
+        assert_eq!(
+            parse("-c answers.txt -f json").err().unwrap(),
+            "--check can't be combined with --format"
+        );
+        assert!(parse("-c answers.txt -p").is_ok());
+        assert!(parse("-p -f json").is_ok());
+    }
+
+    #[test]
+    fn test_parse_help() {
+        assert!(matches!(
+            parse_args(vec![String::from("1"), String::from("-h")].into_iter()),
+            Ok(Command::Help)
+        ))
+    }
+}
@@ -0,0 +1,340 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::{solution::millis, try_parse, Error, Part, Result, Solver, Timings};
+
+/// A median this much slower than the baseline is reported as a regression.
+pub const REGRESSION_THRESHOLD: f64 = 0.1;
+/// It also has to be at least this much slower, since days that take microseconds vary more
+/// than the threshold between runs.
+pub const REGRESSION_MIN_DELTA: Duration = Duration::from_micros(50);
+
+const PHASES: [&str; 4] = ["parse", "part_1", "part_2", "total"];
+
+/// Summary of the samples of one phase.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        let n = nanos.len().max(1) as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+
+        let median = match sorted.len() {
+            0 => Duration::default(),
+            len if len % 2 == 0 => (sorted[len / 2 - 1] + sorted[len / 2]) / 2,
+            len => sorted[len / 2],
+        };
+
+        Stats {
+            min: sorted.first().copied().unwrap_or_default(),
+            median,
+            mean: Duration::from_nanos(mean as u64),
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+        }
+    }
+}
+
+/// Timings of repeated runs of one day, without reading the input.
+#[derive(Debug, Clone)]
+pub struct Bench {
+    pub day: u8,
+    pub samples: Vec<Timings>,
+}
+
+impl Bench {
+    /// Solve the day `runs` times after a few warmup runs that are not measured.
+    pub fn run(solver: &dyn Solver, input: &str, runs: usize, part: Option<Part>) -> Result<Bench> {
+        for _ in 0..(runs / 10).max(1) {
+            solver.solve_part(input, part)?;
+        }
+
+        let samples = (0..runs)
+            .map(|_| {
+                solver
+                    .solve_part(input, part)
+                    .map(|solution| solution.timings())
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Bench {
+            day: solver.metadata().day,
+            samples,
+        })
+    }
+
+    /// Stats of each phase in the order parse, part 1, part 2 and total.
+    pub fn stats(&self) -> [(&'static str, Stats); 4] {
+        let phase = |f: fn(&Timings) -> Duration| {
+            Stats::new(&self.samples.iter().map(f).collect::<Vec<_>>())
+        };
+
+        [
+            ("parse", phase(|t| t.parse)),
+            ("part_1", phase(|t| t.part_1)),
+            ("part_2", phase(|t| t.part_2)),
+            ("total", phase(Timings::total)),
+        ]
+    }
+
+    /// Compare the median of each phase with the baseline.
+    pub fn compare(&self, baseline: &Baseline) -> Vec<Comparison> {
+        self.stats()
+            .iter()
+            .filter_map(|&(phase, stats)| {
+                baseline.median(self.day, phase).map(|before| Comparison {
+                    day: self.day,
+                    phase,
+                    before,
+                    after: stats.median,
+                })
+            })
+            .collect()
+    }
+
+    pub fn view_all(benches: &[Bench]) {
+        println!(
+            "| {: >3} | {: <6} | {: >10} | {: >10} | {: >10} | {: >10} |",
+            "Day", "Phase", "Min ms", "Median ms", "Mean ms", "Stddev ms"
+        );
+        benches.iter().for_each(|bench| print!("{}", bench));
+    }
+}
+
+impl Display for Bench {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (phase, stats) in self.stats() {
+            writeln!(
+                f,
+                "|  {: >2} | {: <6} | {: >10.3} | {: >10.3} | {: >10.3} | {: >10.3} |",
+                self.day,
+                phase,
+                millis(stats.min),
+                millis(stats.median),
+                millis(stats.mean),
+                millis(stats.stddev),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Medians of an earlier benchmark, one line per phase: `DAY PHASE NANOSECONDS`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Baseline {
+    medians: HashMap<(u8, String), Duration>,
+}
+
+impl Baseline {
+    pub fn new(benches: &[Bench]) -> Baseline {
+        let medians = benches
+            .iter()
+            .flat_map(|bench| {
+                bench
+                    .stats()
+                    .map(|(phase, stats)| ((bench.day, phase.to_string()), stats.median))
+            })
+            .collect();
+
+        Baseline { medians }
+    }
+
+    pub fn median(&self, day: u8, phase: &str) -> Option<Duration> {
+        self.medians.get(&(day, phase.to_string())).copied()
+    }
+}
+
+impl FromStr for Baseline {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Baseline> {
+        let mut medians = HashMap::new();
+
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let columns: Vec<&str> = line.split_whitespace().collect();
+
+            if columns.len() != 3 {
+                return Err(Error::parse(line, "DAY PHASE NANOSECONDS"));
+            }
+            if !PHASES.contains(&columns[1]) {
+                return Err(Error::parse(columns[1], "parse, part_1, part_2 or total"));
+            }
+
+            medians.insert(
+                (try_parse(columns[0])?, columns[1].to_string()),
+                Duration::from_nanos(try_parse(columns[2])?),
+            );
+        }
+
+        Ok(Baseline { medians })
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut keys: Vec<_> = self.medians.keys().collect();
+        keys.sort_by_key(|(day, phase)| (*day, PHASES.iter().position(|p| p == phase)));
+
+        for key in keys {
+            writeln!(f, "{} {} {}", key.0, key.1, self.medians[key].as_nanos())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Median of a phase before and after a change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comparison {
+    pub day: u8,
+    pub phase: &'static str,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Comparison {
+    /// Relative change of the median, positive when it got slower.
+    pub fn change(&self) -> f64 {
+        if self.before.is_zero() {
+            return 0.0;
+        }
+        self.after.as_nanos() as f64 / self.before.as_nanos() as f64 - 1.0
+    }
+
+    pub fn is_regression(&self) -> bool {
+        self.change() > REGRESSION_THRESHOLD
+            && self.after.saturating_sub(self.before) >= REGRESSION_MIN_DELTA
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Day {: >2} {: <6}: {: >10.3} ms -> {: >10.3} ms ({:+.1}%)",
+            self.day,
+            self.phase,
+            millis(self.before),
+            millis(self.after),
+            self.change() * 100.0
+        )?;
+
+        if self.is_regression() {
+            write!(f, " REGRESSION")?;
+        }
+
+        Ok(())
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::day::TestDay;
+
+    fn durations(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    fn bench(day: u8, parse: u64) -> Bench {
+        let timings = Timings {
+            parse: Duration::from_millis(parse),
+            part_1: Duration::from_millis(2),
+            part_2: Duration::from_millis(3),
+            ..Timings::default()
+        };
+        Bench {
+            day,
+            samples: vec![timings; 3],
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::new(&durations(&[4, 2, 9, 5]));
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.median, Duration::from_micros(4500));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        // Sample standard deviation of 2, 4, 5 and 9 is sqrt(26 / 3).
+        assert_eq!(stats.stddev.as_micros(), 2943);
+    }
+
+    #[test]
+    fn test_stats_single_sample() {
+        let stats = Stats::new(&durations(&[3]));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.stddev, Duration::default());
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let baseline = Baseline::new(&[bench(2, 1), bench(1, 1)]);
+        let text = baseline.to_string();
+        assert!(text.starts_with("1 parse 1000000\n1 part_1 2000000\n"));
+        assert_eq!(text.parse::<Baseline>().unwrap(), baseline);
+    }
+
+    #[test]
+    fn test_parse_invalid_baseline() {
+        let input = "1 parse 1000\n1 part_3 1000";
+        let error = input.parse::<Baseline>().unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected parse, part_1, part_2 or total, found \"part_3\""
+        )
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = Baseline::new(&[bench(1, 10)]);
+        let comparisons = bench(1, 12).compare(&baseline);
+
+        assert_eq!(comparisons.len(), 4);
+        assert!(comparisons[0].is_regression());
+        assert_eq!(
+            comparisons[0].to_string(),
+            "Day  1 parse :     10.000 ms ->     12.000 ms (+20.0%) REGRESSION"
+        );
+        assert!(!comparisons[1].is_regression());
+    }
+
+    #[test]
+    fn test_compare_fast_phase() {
+        let comparison = |before, after| Comparison {
+            day: 1,
+            phase: "part_1",
+            before: Duration::from_micros(before),
+            after: Duration::from_micros(after),
+        };
+        // 30% slower, but only by 30µs.
+        assert!(!comparison(100, 130).is_regression());
+        assert!(comparison(100, 150).is_regression());
+        assert!(!comparison(1000, 1050).is_regression());
+    }
+
+    #[test]
+    fn test_run() {
+        let bench = Bench::run(&TestDay(1), "42", 5, None).unwrap();
+        assert_eq!(bench.day, 1);
+        assert_eq!(bench.samples.len(), 5);
+        assert!(Bench::run(&TestDay(1), "x", 5, None).is_err());
+    }
+}
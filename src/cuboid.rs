@@ -0,0 +1,249 @@
+use std::ops::RangeInclusive;
+
+pub type Point3 = [i64; 3];
+
+/// An axis aligned box of integer points, including both corners.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Cuboid {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Cuboid {
+    /// Create a cuboid from the x, y and z ranges. Empty ranges give an empty cuboid.
+    pub fn new(ranges: [RangeInclusive<i64>; 3]) -> Cuboid {
+        Cuboid {
+            min: ranges.clone().map(|range| *range.start()),
+            max: ranges.map(|range| *range.end()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..3).any(|axis| self.min[axis] > self.max[axis])
+    }
+
+    pub fn volume(&self) -> i64 {
+        if self.is_empty() {
+            return 0;
+        }
+        (0..3)
+            .map(|axis| self.max[axis] - self.min[axis] + 1)
+            .product()
+    }
+
+    pub fn contains(&self, point: Point3) -> bool {
+        (0..3).all(|axis| (self.min[axis]..=self.max[axis]).contains(&point[axis]))
+    }
+
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let intersection = Cuboid {
+            min: [0, 1, 2].map(|axis| self.min[axis].max(other.min[axis])),
+            max: [0, 1, 2].map(|axis| self.max[axis].min(other.max[axis])),
+        };
+
+        if intersection.is_empty() {
+            None
+        } else {
+            Some(intersection)
+        }
+    }
+
+    /// Split this cuboid into at most 6 disjoint cuboids that cover everything outside of `other`.
+    pub fn difference(&self, other: &Cuboid) -> Vec<Cuboid> {
+        let overlap = match self.intersection(other) {
+            Some(overlap) => overlap,
+            None if self.is_empty() => return vec![],
+            None => return vec![*self],
+        };
+        let mut rest = *self;
+        let mut pieces = vec![];
+
+        // Cut off the slabs below and above the overlap, one axis at a time.
+        for axis in 0..3 {
+            if rest.min[axis] < overlap.min[axis] {
+                let mut below = rest;
+                below.max[axis] = overlap.min[axis] - 1;
+                pieces.push(below);
+                rest.min[axis] = overlap.min[axis];
+            }
+            if rest.max[axis] > overlap.max[axis] {
+                let mut above = rest;
+                above.min[axis] = overlap.max[axis] + 1;
+                pieces.push(above);
+                rest.max[axis] = overlap.max[axis];
+            }
+        }
+
+        pieces
+    }
+}
+
+/// A set of integer points stored as disjoint cuboids.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CuboidSet {
+    cuboids: Vec<Cuboid>,
+}
+
+impl CuboidSet {
+    pub fn new() -> CuboidSet {
+        CuboidSet { cuboids: vec![] }
+    }
+
+    /// The disjoint cuboids of the set, in no particular order.
+    pub fn cuboids(&self) -> &[Cuboid] {
+        &self.cuboids
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cuboids.is_empty()
+    }
+
+    /// The number of points in the set.
+    pub fn volume(&self) -> i64 {
+        self.cuboids.iter().map(Cuboid::volume).sum()
+    }
+
+    pub fn contains(&self, point: Point3) -> bool {
+        self.cuboids.iter().any(|cuboid| cuboid.contains(point))
+    }
+
+    /// Add all points of the cuboid.
+    pub fn insert(&mut self, cuboid: Cuboid) {
+        self.remove(&cuboid);
+        if !cuboid.is_empty() {
+            self.cuboids.push(cuboid);
+        }
+    }
+
+    /// Remove all points of the cuboid.
+    pub fn remove(&mut self, cuboid: &Cuboid) {
+        self.cuboids = self
+            .cuboids
+            .iter()
+            .flat_map(|c| c.difference(cuboid))
+            .collect();
+    }
+
+    pub fn union(&self, other: &CuboidSet) -> CuboidSet {
+        let mut union = self.clone();
+        other
+            .cuboids
+            .iter()
+            .for_each(|cuboid| union.insert(*cuboid));
+        union
+    }
+
+    pub fn difference(&self, other: &CuboidSet) -> CuboidSet {
+        let mut difference = self.clone();
+        other
+            .cuboids
+            .iter()
+            .for_each(|cuboid| difference.remove(cuboid));
+        difference
+    }
+
+    pub fn intersection(&self, other: &CuboidSet) -> CuboidSet {
+        // The intersections of two sets of disjoint cuboids are disjoint as well.
+        let cuboids = self
+            .cuboids
+            .iter()
+            .flat_map(|a| other.cuboids.iter().filter_map(move |b| a.intersection(b)))
+            .collect();
+
+        CuboidSet { cuboids }
+    }
+
+    /// Only keep the points inside the region.
+    pub fn clip(&self, region: &Cuboid) -> CuboidSet {
+        let cuboids = self
+            .cuboids
+            .iter()
+            .filter_map(|cuboid| cuboid.intersection(region))
+            .collect();
+
+        CuboidSet { cuboids }
+    }
+}
+
+impl From<Cuboid> for CuboidSet {
+    fn from(cuboid: Cuboid) -> CuboidSet {
+        let mut set = CuboidSet::new();
+        set.insert(cuboid);
+        set
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    fn cube(from: i64, to: i64) -> Cuboid {
+        Cuboid::new([from..=to, from..=to, from..=to])
+    }
+
+    #[test]
+    fn test_volume() {
+        assert_eq!(cube(10, 12).volume(), 27);
+        let empty = Cuboid {
+            min: [0, 5, 0],
+            max: [1, 4, 1],
+        };
+        assert_eq!(empty.volume(), 0);
+    }
+
+    #[test]
+    fn test_intersection() {
+        assert_eq!(
+            cube(0, 2).intersection(&Cuboid::new([2..=5, -1..=1, 1..=1])),
+            Some(Cuboid::new([2..=2, 0..=1, 1..=1]))
+        );
+        assert_eq!(cube(0, 2).intersection(&cube(3, 5)), None);
+    }
+
+    #[test]
+    fn test_cuboid_difference() {
+        let pieces = cube(0, 2).difference(&cube(1, 1));
+        assert_eq!(pieces.len(), 6);
+        assert_eq!(pieces.iter().map(Cuboid::volume).sum::<i64>(), 26);
+        assert!(pieces.iter().all(|piece| !piece.contains([1, 1, 1])));
+
+        assert_eq!(cube(0, 2).difference(&cube(-5, 5)), vec![]);
+        assert_eq!(cube(0, 2).difference(&cube(3, 5)), vec![cube(0, 2)]);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = CuboidSet::from(cube(0, 2));
+        let b = CuboidSet::from(cube(1, 3));
+
+        assert_eq!(a.union(&b).volume(), 27 + 27 - 8);
+        assert_eq!(a.intersection(&b).volume(), 8);
+        assert_eq!(a.difference(&b).volume(), 27 - 8);
+        assert_eq!(b.difference(&a).volume(), 27 - 8);
+        assert!(a.union(&b).contains([3, 3, 3]));
+        assert!(!a.difference(&b).contains([1, 1, 1]));
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut set = CuboidSet::new();
+        set.insert(cube(0, 9));
+        set.insert(cube(5, 14));
+        set.remove(&cube(-5, 4));
+        set.insert(cube(0, 0));
+
+        assert_eq!(set.volume(), 1000 + 1000 - 125 - 125 + 1);
+        assert!(set.contains([0, 0, 0]));
+        assert!(!set.contains([1, 1, 1]));
+    }
+
+    #[test]
+    fn test_clip() {
+        let set = CuboidSet::from(cube(-100, 100));
+        assert_eq!(set.clip(&cube(-50, 50)).volume(), 101 * 101 * 101);
+        assert!(set.clip(&cube(200, 300)).is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+use std::{
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::{Error, Result, Solution, Timings};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metadata {
+    pub day: u8,
+    pub title: &'static str,
+}
+
+impl Metadata {
+    pub fn new(day: u8, title: &'static str) -> Metadata {
+        Metadata { day, title }
+    }
+
+    pub fn input_path(&self) -> String {
+        format!("./input/day_{:02}.txt", self.day)
+    }
+}
+
+/// One of the two parts of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(part: &str) -> Result<Part> {
+        match part {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(Error::parse(part, "part 1 or 2")),
+        }
+    }
+}
+
+/// A puzzle solution. Implement this to add a day to a `Registry`.
+/// Days are `Send + Sync` so that they can be solved on several threads at once.
+pub trait Day: Send + Sync {
+    type Input;
+
+    fn metadata(&self) -> Metadata;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+
+    fn part1(&self, input: &Self::Input) -> String;
+
+    fn part2(&self, input: &Self::Input) -> String;
+}
+
+/// Object safe version of `Day` that hides the parsed input type,
+/// which makes it possible to keep days of different input types in one list.
+pub trait Solver: Send + Sync {
+    fn metadata(&self) -> Metadata;
+
+    fn solve(&self, input: &str) -> Result<Solution> {
+        self.solve_part(input, None)
+    }
+
+    /// Solve only one part, or both if `part` is `None`. The answer of a skipped part is "-".
+    fn solve_part(&self, input: &str, part: Option<Part>) -> Result<Solution>;
+}
+
+impl<D: Day> Solver for D {
+    fn metadata(&self) -> Metadata {
+        Day::metadata(self)
+    }
+
+    fn solve_part(&self, input: &str, part: Option<Part>) -> Result<Solution> {
+        let timer = Instant::now();
+        // Piped input and files saved by editors often end with a newline that the parsers don't expect.
+        let parsed = self
+            .parse(input.trim_end())
+            .map_err(|error| error.locate(input))?;
+        let parse = timer.elapsed();
+
+        let (part_1, part_1_time) = match part {
+            Some(Part::Two) => (String::from("-"), Default::default()),
+            _ => timed(|| self.part1(&parsed)),
+        };
+        let (part_2, part_2_time) = match part {
+            Some(Part::One) => (String::from("-"), Default::default()),
+            _ => timed(|| self.part2(&parsed)),
+        };
+
+        let timings = Timings {
+            read: Default::default(),
+            parse,
+            part_1: part_1_time,
+            part_2: part_2_time,
+        };
+
+        Ok(Solution::new(
+            Day::metadata(self).day,
+            part_1,
+            part_2,
+            timings,
+        ))
+    }
+}
+
+fn timed<F: FnOnce() -> String>(f: F) -> (String, Duration) {
+    let timer = Instant::now();
+    let answer = f();
+    (answer, timer.elapsed())
+}
+
+/* Tests */
+
+/// A day to test registries and benchmarks with. It parses a number, answers it in part 1
+/// and doubles it in part 2.
+#[cfg(test)]
+pub(crate) struct TestDay(pub u8);
+
+#[cfg(test)]
+impl Day for TestDay {
+    type Input = usize;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(self.0, "Test")
+    }
+
+    fn parse(&self, input: &str) -> Result<usize> {
+        crate::try_parse(input)
+    }
+
+    fn part1(&self, input: &usize) -> String {
+        input.to_string()
+    }
+
+    fn part2(&self, input: &usize) -> String {
+        (input * 2).to_string()
+    }
+}
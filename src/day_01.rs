@@ -1,23 +1,40 @@
-use crate::{read_number_input, Solution};
+use crate::{to_numbers, Day, Metadata, Result};
 
-fn part01(input: &Vec<u16>) -> usize {
+fn part01(input: &[u16]) -> usize {
     input.windows(2).filter(|n| n[0] < n[1]).count()
 }
 
-fn part02(input: &Vec<u16>) -> usize {
+fn part02(input: &[u16]) -> usize {
     input.windows(4).filter(|n| n[0] < n[3]).count()
 }
 
-pub fn day_01() -> Solution {
-    let input = read_number_input("./input/day_01.txt");
-    let timer = std::time::Instant::now();
-    Solution::new(1, part01(&input), part02(&input), timer.elapsed())
+pub struct Day01;
+
+impl Day for Day01 {
+    type Input = Vec<u16>;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(1, "Sonar Sweep")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        to_numbers(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read_number_input;
 
     #[test]
     fn test_part01() {
@@ -1,4 +1,4 @@
-use crate::{read, Solution};
+use crate::{try_parse, try_split_once, Day, Error, Metadata, Result};
 
 // DIRECTIONS
 
@@ -10,49 +10,83 @@ pub enum Direction {
 }
 
 impl Direction {
-    pub fn from_str(input: &str) -> Direction {
-        let (direction, value) = input.split_once(" ").unwrap();
-        let n = value.parse::<u32>().unwrap();
+    pub fn from_str(input: &str) -> Result<Direction> {
+        let (direction, value) = try_split_once(input, " ")?;
+        let n = try_parse::<u32>(value)?;
 
         match direction {
-            "forward" => Direction::Forward(n),
-            "down" => Direction::Down(n),
-            _ => Direction::Up(n),
+            "forward" => Ok(Direction::Forward(n)),
+            "down" => Ok(Direction::Down(n)),
+            "up" => Ok(Direction::Up(n)),
+            _ => Err(Error::parse(direction, "forward, down or up")),
         }
     }
 }
 
-fn read_directions(path: &str) -> Vec<Direction> {
-    read(path).lines().map(Direction::from_str).collect()
+fn parse_directions(input: &str) -> Result<Vec<Direction>> {
+    input.lines().map(Direction::from_str).collect()
 }
 
 // Solutions
 
-fn part01(directions: &Vec<Direction>) -> u32 {
+// The submarine can go up above the surface, so depths and aims are signed.
+
+fn part01(directions: &[Direction]) -> i64 {
     let (position, depth) = directions
         .iter()
-        .fold((0, 0), |(position, depth), direction| match direction {
-            Direction::Forward(n) => (position + n, depth),
-            Direction::Down(n) => (position, depth + n),
-            Direction::Up(n) => (position, depth - n),
+        .fold((0, 0), |(position, depth), direction| match *direction {
+            Direction::Forward(n) => (position + i64::from(n), depth),
+            Direction::Down(n) => (position, depth + i64::from(n)),
+            Direction::Up(n) => (position, depth - i64::from(n)),
         });
     position * depth
 }
 
-fn part02(directions: &Vec<Direction>) -> u32 {
+fn part02(directions: &[Direction]) -> i64 {
     let (position, depth, _) = directions.iter().fold(
         (0, 0, 0),
-        |(position, depth, aim), direction| match direction {
-            Direction::Forward(n) => (position + n, depth + (aim * n), aim),
-            Direction::Down(n) => (position, depth, aim + n),
-            Direction::Up(n) => (position, depth, aim - n),
+        |(position, depth, aim), direction| match *direction {
+            Direction::Forward(n) => (position + i64::from(n), depth + aim * i64::from(n), aim),
+            Direction::Down(n) => (position, depth, aim + i64::from(n)),
+            Direction::Up(n) => (position, depth, aim - i64::from(n)),
         },
     );
     position * depth
 }
 
-pub fn day_02() -> Solution {
-    let input = read_directions("./input/day_02.txt");
-    let timer = std::time::Instant::now();
-    Solution::new(2, part01(&input), part02(&input), timer.elapsed())
+pub struct Day02;
+
+impl Day for Day02 {
+    type Input = Vec<Direction>;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(2, "Dive!")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_directions(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_above_surface() {
+        let directions = parse_directions("up 5\nforward 2").unwrap();
+        assert_eq!(part01(&directions), -10);
+        assert_eq!(part02(&directions), -20);
+    }
 }
@@ -1,11 +1,16 @@
-use crate::{read_lines, to_columns, Solution};
+use std::collections::HashSet;
 
-fn one_is_most_common(input: &Vec<&str>) -> bool {
+use crate::{check_chars, to_columns, to_lines, Day, Error, Metadata, Result};
+
+// The rates and ratings are u32, and their products as well.
+const MAX_WIDTH: usize = 16;
+
+fn one_is_most_common(input: &[&str]) -> bool {
     let (ones, zeros): (Vec<&str>, Vec<&str>) = input.iter().partition(|x| **x == "1");
     ones.len() >= zeros.len()
 }
 
-fn most_common<'a>(input: &Vec<&str>) -> &'a str {
+fn most_common<'a>(input: &[&str]) -> &'a str {
     if one_is_most_common(input) {
         "1"
     } else {
@@ -13,7 +18,7 @@ fn most_common<'a>(input: &Vec<&str>) -> &'a str {
     }
 }
 
-fn least_common<'a>(input: &Vec<&str>) -> &'a str {
+fn least_common<'a>(input: &[&str]) -> &'a str {
     if one_is_most_common(input) {
         "0"
     } else {
@@ -25,15 +30,42 @@ fn to_decimal(x: String) -> u32 {
     isize::from_str_radix(x.as_str(), 2).unwrap() as u32
 }
 
-fn split_binaries<'a>(input: &'a Vec<String>) -> Vec<Vec<&'a str>> {
+fn parse_binaries(input: &str) -> Result<Vec<String>> {
+    let width = input.lines().next().map_or(0, str::len);
+    let mut seen = HashSet::new();
+
+    if width == 0 {
+        return Err(Error::parse(input, "a binary number"));
+    }
+    for line in input.lines() {
+        check_chars(line, |c| c == '0' || c == '1', "a binary digit")?;
+        if line.len() != width {
+            return Err(Error::parse(line, &format!("{} binary digits", width)));
+        }
+        if width > MAX_WIDTH {
+            return Err(Error::parse(
+                line,
+                &format!("at most {} binary digits", MAX_WIDTH),
+            ));
+        }
+        // The ratings filter the numbers until one is left, which never happens for duplicates.
+        if !seen.insert(line) {
+            return Err(Error::parse(line, "a binary number that isn't listed yet"));
+        }
+    }
+
+    Ok(to_lines(input))
+}
+
+fn split_binaries(input: &[String]) -> Vec<Vec<&str>> {
     input
         .iter()
         .map(|s| s.split("").filter(|c| !c.is_empty()).collect::<Vec<&str>>())
         .collect::<Vec<Vec<&str>>>()
 }
 
-fn get_rating<'a, F: Fn(&Vec<&str>) -> &'a str>(f: F, input: &Vec<Vec<&str>>) -> u32 {
-    let mut binaries = input.clone();
+fn get_rating<'a, F: Fn(&[&str]) -> &'a str>(f: F, input: &[Vec<&str>]) -> u32 {
+    let mut binaries = input.to_vec();
     let mut i = 0;
     let mut j = 0;
 
@@ -41,26 +73,25 @@ fn get_rating<'a, F: Fn(&Vec<&str>) -> &'a str>(f: F, input: &Vec<Vec<&str>>) ->
         let x = f(to_columns(&binaries).get(i).unwrap());
 
         binaries = binaries
-            .to_owned()
             .iter()
             .filter(|&binary| *binary.get(j).unwrap() == x)
             .map(|b| b.to_owned())
             .collect();
 
-        i = i + 1;
-        j = j + 1;
+        i += 1;
+        j += 1;
     }
 
-    to_decimal(binaries.get(0).unwrap().join(""))
+    to_decimal(binaries.first().unwrap().join(""))
 }
 
-fn part01(input: &Vec<String>) -> u32 {
+fn part01(input: &[String]) -> u32 {
     let (gamma_rate, epsilon_rate) = to_columns(&split_binaries(input)).iter().fold(
         (String::from(""), String::from("")),
         |(gamma_rate, epsilon_rate), column| {
             (
-                gamma_rate + most_common(&column),
-                epsilon_rate + least_common(&column),
+                gamma_rate + most_common(column),
+                epsilon_rate + least_common(column),
             )
         },
     );
@@ -68,7 +99,7 @@ fn part01(input: &Vec<String>) -> u32 {
     to_decimal(gamma_rate) * to_decimal(epsilon_rate)
 }
 
-fn part02(input: &Vec<String>) -> u32 {
+fn part02(input: &[String]) -> u32 {
     let parsed = split_binaries(input);
 
     let oxygen = get_rating(most_common, &parsed);
@@ -77,10 +108,26 @@ fn part02(input: &Vec<String>) -> u32 {
     oxygen * co2
 }
 
-pub fn day_03() -> Solution {
-    let input = read_lines("./input/day_03.txt");
-    let timer = std::time::Instant::now();
-    Solution::new(3, part01(&input), part02(&input), timer.elapsed())
+pub struct Day03;
+
+impl Day for Day03 {
+    type Input = Vec<String>;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(3, "Binary Diagnostic")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_binaries(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 #[cfg(test)]
@@ -106,6 +153,20 @@ mod tests {
             ]
         );
     }
+    #[test]
+    fn test_parse_invalid_binaries() {
+        assert!(parse_binaries("").is_err());
+        assert!(parse_binaries("10101010101010101").is_err());
+
+        let input = "0110\n1001\n0110";
+        let error = parse_binaries(input).unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected a binary number that isn't listed yet, found \"0110\""
+        );
+        assert_eq!(part02(&parse_binaries("0\n1").unwrap()), 0);
+    }
+
     #[test]
     fn test_part01() {
         let input = vec![
@@ -1,45 +1,51 @@
-use crate::{read, to_columns, Solution};
+use crate::{to_columns, try_parse, try_split_once, Day, Error, Metadata, Result};
 
 #[derive(Debug)]
-struct Board {
+pub struct Board {
     board: Vec<Vec<u8>>,
     all: Vec<u8>,
 }
 
 impl Board {
-    fn new(input: &str) -> Board {
+    fn new(input: &str) -> Result<Board> {
         let rows = input
             .lines()
             .map(|line| {
-                line.split(" ")
-                    .filter_map(|c| {
-                        if c.is_empty() {
-                            None
-                        } else {
-                            Some(c.parse().unwrap())
-                        }
-                    })
-                    .collect::<Vec<u8>>()
+                let row = line
+                    .split(" ")
+                    .filter(|c| !c.is_empty())
+                    .map(try_parse)
+                    .collect::<Result<Vec<u8>>>()?;
+
+                if row.len() == 5 {
+                    Ok(row)
+                } else {
+                    Err(Error::parse(line, "a row of 5 numbers"))
+                }
             })
-            .collect::<Vec<Vec<u8>>>();
+            .collect::<Result<Vec<Vec<u8>>>>()?;
+
+        if rows.len() != 5 {
+            return Err(Error::parse(input, "a board of 5 rows"));
+        }
 
         let cols = to_columns(&rows);
 
-        let all = rows.iter().flatten().map(|x| *x).collect();
+        let all = rows.iter().flatten().copied().collect();
 
-        Board {
-            board: rows.into_iter().chain(cols.into_iter()).collect(),
-            all: all,
-        }
+        Ok(Board {
+            board: rows.into_iter().chain(cols).collect(),
+            all,
+        })
     }
 
-    fn is_winner(&self, numbers: &Vec<u8>) -> bool {
+    fn is_winner(&self, numbers: &[u8]) -> bool {
         self.board
             .iter()
             .any(|xs| xs.iter().filter(|&x| numbers.contains(x)).count() == 5)
     }
 
-    fn get_winning_score(&self, numbers: &Vec<u8>) -> Option<u32> {
+    fn get_winning_score(&self, numbers: &[u8]) -> Option<u32> {
         if self.is_winner(numbers) {
             Some(
                 self.all
@@ -54,14 +60,26 @@ impl Board {
     }
 }
 
-fn read_input() -> (Vec<Board>, Vec<u8>) {
-    let input = read("./input/day_04.txt");
-    let (numbers, boards) = input.split_once("\n\n").unwrap();
+fn parse_input(input: &str) -> Result<(Vec<Board>, Vec<u8>)> {
+    let (numbers, boards) = try_split_once(input, "\n\n")?;
 
-    let numbers: Vec<u8> = numbers.split(",").map(|n| n.parse().unwrap()).collect();
-    let boards: Vec<Board> = boards.split("\n\n").map(Board::new).collect();
+    let line = numbers;
+    let numbers: Vec<u8> = numbers.split(",").map(try_parse).collect::<Result<_>>()?;
+    let boards: Vec<Board> = boards
+        .split("\n\n")
+        .map(Board::new)
+        .collect::<Result<_>>()?;
+
+    // A board can only win once a whole row or column is drawn.
+    if numbers.len() < 5 {
+        return Err(Error::parse(line, "at least 5 numbers"));
+    }
+    let input = (boards, numbers);
+    if get_winning_boards(&input).is_empty() {
+        return Err(Error::parse(line, "numbers that let a board win"));
+    }
 
-    (boards, numbers)
+    Ok(input)
 }
 
 fn get_winning_boards((boards, numbers): &(Vec<Board>, Vec<u8>)) -> Vec<(usize, u32, u8)> {
@@ -105,10 +123,26 @@ fn part02(input: &(Vec<Board>, Vec<u8>)) -> u32 {
     score * (winning_number as u32)
 }
 
-pub fn day_04() -> Solution {
-    let input = read_input();
-    let timer = std::time::Instant::now();
-    Solution::new(4, part01(&input), part02(&input), timer.elapsed())
+pub struct Day04;
+
+impl Day for Day04 {
+    type Input = (Vec<Board>, Vec<u8>);
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(4, "Giant Squid")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 #[cfg(test)]
@@ -124,7 +158,8 @@ mod tests {
             18  8 23 26 20
             22 11 13  6  5
             2  0 12  3  7"#,
-        );
+        )
+        .unwrap();
 
         let numbers = vec![7, 4, 9, 5, 11, 17, 23, 2, 0, 14, 21, 24];
 
@@ -139,10 +174,33 @@ mod tests {
     18  8 23 26 20
     22 11 13  6  5
     2  0 12  3  7"#,
-        );
+        )
+        .unwrap();
 
         let numbers = vec![7, 4, 9, 5, 11, 17, 23, 2, 0, 14, 21, 24];
 
         assert_eq!(play(&board, numbers), None);
     }
+
+    #[test]
+    fn test_parse_without_winner() {
+        let board = "1 2 3 4 5\n6 7 8 9 10\n11 12 13 14 15\n16 17 18 19 20\n21 22 23 24 25";
+
+        let input = format!("1,2,3,4\n\n{}", board);
+        let error = parse_input(&input).unwrap_err().locate(&input);
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected at least 5 numbers, found \"1,2,3,4\""
+        );
+
+        let input = format!("1,2,3,4,6\n\n{}", board);
+        let error = parse_input(&input).unwrap_err().locate(&input);
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected numbers that let a board win, found \"1,2,3,4,6\""
+        );
+
+        let input = format!("1,2,3,4,5\n\n{}", board);
+        assert_eq!(part02(&parse_input(&input).unwrap()), 310 * 5);
+    }
 }
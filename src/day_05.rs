@@ -1,17 +1,19 @@
 use std::collections::HashMap;
 
-use crate::{map_pair, read_lines, Solution};
+use crate::{map_pair, try_parse, try_split_once, Day, Metadata, Result};
 
 #[derive(Debug, PartialEq)]
-enum Line {
+pub enum Line {
     Straight(Vec<Coordinate>),
     Diagonal(Vec<Coordinate>),
 }
 
 impl Line {
-    fn new(segment: &String) -> Option<Line> {
-        let (c1, c2) = map_pair(Coordinate::from_str, segment.split_once(" -> ").unwrap());
-        if Line::is_diagonal(c1, c2) {
+    fn new(segment: &str) -> Result<Option<Line>> {
+        let (c1, c2) = map_pair(Coordinate::from_str, try_split_once(segment, " -> ")?);
+        let (c1, c2) = (c1?, c2?);
+
+        Ok(if Line::is_diagonal(c1, c2) {
             Some(Line::to_diagonal(c1, c2))
         } else if Line::is_horizontal(c1, c2) {
             Some(Line::to_horizontal(c1, c2))
@@ -19,7 +21,7 @@ impl Line {
             Some(Line::to_vertical(c1, c2))
         } else {
             None
-        }
+        })
     }
 
     fn is_horizontal(c1: Coordinate, c2: Coordinate) -> bool {
@@ -78,7 +80,7 @@ fn to_range(x: i16, y: i16) -> Vec<i16> {
 }
 
 #[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord, Hash, Copy)]
-struct Coordinate {
+pub struct Coordinate {
     x: i16,
     y: i16,
 }
@@ -92,9 +94,9 @@ impl Coordinate {
         Coordinate::new(x.to_owned(), y.to_owned())
     }
 
-    fn from_str(str: &str) -> Coordinate {
-        let (x, y) = str.split_once(",").unwrap();
-        Coordinate::new(x.parse().unwrap(), y.parse().unwrap())
+    fn from_str(str: &str) -> Result<Coordinate> {
+        let (x, y) = try_split_once(str, ",")?;
+        Ok(Coordinate::new(try_parse(x)?, try_parse(y)?))
     }
 
     fn range(xs: Vec<i16>, ys: Vec<i16>) -> Vec<Coordinate> {
@@ -121,26 +123,43 @@ fn number_of_overlapping_coordinates(coordinates: Vec<Coordinate>) -> usize {
 
 fn get_coordinates<F: Fn(&Line) -> Option<Vec<Coordinate>>>(
     getter: F,
-    lines: &Vec<Line>,
+    lines: &[Line],
 ) -> Vec<Coordinate> {
     lines.iter().filter_map(getter).flatten().collect()
 }
 
-fn part01(lines: &Vec<Line>) -> usize {
+fn part01(lines: &[Line]) -> usize {
     number_of_overlapping_coordinates(get_coordinates(Line::straight_coordinates, lines))
 }
 
-fn part02(lines: &Vec<Line>) -> usize {
+fn part02(lines: &[Line]) -> usize {
     number_of_overlapping_coordinates(get_coordinates(Line::all_coordinates, lines))
 }
 
-pub fn day_05() -> Solution {
-    let input = read_lines("./input/day_05.txt")
-        .iter()
-        .filter_map(Line::new)
-        .collect();
-    let timer = std::time::Instant::now();
-    Solution::new(5, part01(&input), part02(&input), timer.elapsed())
+pub struct Day05;
+
+impl Day for Day05 {
+    type Input = Vec<Line>;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(5, "Hydrothermal Venture")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(Line::new)
+            .filter_map(Result::transpose)
+            .collect()
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 #[cfg(test)]
@@ -151,7 +170,7 @@ mod tests {
     #[test]
     fn test_positive_horizontal_line() {
         assert_eq!(
-            Line::new(&String::from("0,9 -> 5,9")),
+            Line::new(&String::from("0,9 -> 5,9")).unwrap(),
             Some(Line::Straight(vec![
                 Coordinate::new(5, 9),
                 Coordinate::new(4, 9),
@@ -166,7 +185,7 @@ mod tests {
     #[test]
     fn test_negative_horizontal_line() {
         assert_eq!(
-            Line::new(&String::from("9,7 -> 7,7")),
+            Line::new(&String::from("9,7 -> 7,7")).unwrap(),
             Some(Line::Straight(vec![
                 Coordinate::new(7, 7),
                 Coordinate::new(8, 7),
@@ -178,7 +197,7 @@ mod tests {
     #[test]
     fn test_positive_vertical_line() {
         assert_eq!(
-            Line::new(&String::from("0,3 -> 0,7")),
+            Line::new(&String::from("0,3 -> 0,7")).unwrap(),
             Some(Line::Straight(vec![
                 Coordinate::new(0, 7),
                 Coordinate::new(0, 6),
@@ -192,7 +211,7 @@ mod tests {
     #[test]
     fn test_negative_vertical_line() {
         assert_eq!(
-            Line::new(&String::from("9,7 -> 9,5")),
+            Line::new(&String::from("9,7 -> 9,5")).unwrap(),
             Some(Line::Straight(vec![
                 Coordinate::new(9, 5),
                 Coordinate::new(9, 6),
@@ -204,7 +223,7 @@ mod tests {
     #[test]
     fn test_diagonal_line_1() {
         assert_eq!(
-            Line::new(&String::from("1,1 -> 3,3")),
+            Line::new(&String::from("1,1 -> 3,3")).unwrap(),
             Some(Line::Diagonal(vec![
                 Coordinate::new(3, 3),
                 Coordinate::new(2, 2),
@@ -216,7 +235,7 @@ mod tests {
     #[test]
     fn test_diagonal_line_2() {
         assert_eq!(
-            Line::new(&String::from("8,0 -> 0,8")),
+            Line::new(&String::from("8,0 -> 0,8")).unwrap(),
             Some(Line::Diagonal(vec![
                 Coordinate::new(0, 8),
                 Coordinate::new(1, 7),
@@ -251,7 +270,7 @@ mod tests {
 
     #[test]
     fn test_lines() {
-        let actual: Vec<bool> = vec![
+        let actual: Vec<bool> = [
             (Coordinate::new(0, 9), Coordinate::new(5, 9)),
             (Coordinate::new(8, 0), Coordinate::new(0, 8)),
             (Coordinate::new(9, 4), Coordinate::new(3, 4)),
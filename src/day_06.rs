@@ -1,9 +1,9 @@
 use std::collections::HashMap;
 
-use crate::{read_comma_separated_number_input, Solution};
+use crate::{try_parse, Day, Error, Metadata, Result};
 
 #[derive(Debug, Clone)]
-struct CountdownState {
+pub struct CountdownState {
     new: u64,
     day7: u64,
     day6: u64,
@@ -48,7 +48,9 @@ impl CountdownState {
                     3 => state.day3 = amount,
                     4 => state.day4 = amount,
                     5 => state.day5 = amount,
-                    _ => state.day6 = amount,
+                    6 => state.day6 = amount,
+                    7 => state.day7 = amount,
+                    _ => state.new = amount,
                 }
                 state
             })
@@ -85,6 +87,16 @@ impl CountdownState {
     }
 }
 
+fn parse_timers(input: &str) -> Result<Vec<u32>> {
+    input
+        .split(',')
+        .map(|timer| match try_parse(timer)? {
+            timer @ 0..=8 => Ok(timer),
+            _ => Err(Error::parse(timer, "a timer from 0 to 8")),
+        })
+        .collect()
+}
+
 fn part01(input: &CountdownState) -> u64 {
     input.simulate(80).sum()
 }
@@ -93,8 +105,46 @@ fn part02(input: &CountdownState) -> u64 {
     input.simulate(256).sum()
 }
 
-pub fn day_06() -> Solution {
-    let input = CountdownState::new(read_comma_separated_number_input("./input/day_06.txt"));
-    let timer = std::time::Instant::now();
-    Solution::new(6, part01(&input), part02(&input), timer.elapsed())
+pub struct Day06;
+
+impl Day for Day06 {
+    type Input = CountdownState;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(6, "Lanternfish")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(CountdownState::new(parse_timers(input)?))
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_parse_timers() {
+        let input = "3,8,9";
+        let error = parse_timers(input).unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 5: expected a timer from 0 to 8, found \"9\""
+        );
+        // A new fish takes 9 days to make another one.
+        let state = CountdownState::new(parse_timers("8").unwrap());
+        assert_eq!(state.simulate(9).sum(), 2);
+        assert_eq!(CountdownState::new(parse_timers("6,7").unwrap()).sum(), 2);
+    }
 }
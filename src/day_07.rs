@@ -1,17 +1,17 @@
-use crate::{read_comma_separated_number_input, Solution};
+use crate::{to_comma_separated_numbers, Day, Metadata, Result};
 
 /* Find position */
 
-fn means(input: &Vec<u32>) -> [u32; 2] {
+fn means(input: &[u32]) -> [u32; 2] {
     // Division by 0 is fine as long as the result is a float that will be converted to a u32.
     let mean = input.iter().sum::<u32>() as f32 / input.len() as f32;
     // floor and ceil will turn NaN to 0 when converted to u32.
     [mean.floor() as u32, mean.ceil() as u32]
 }
 
-fn median(input: &Vec<u32>) -> u32 {
+fn median(input: &[u32]) -> u32 {
     let index = input.len() / 2;
-    let mut numbers = input.clone();
+    let mut numbers = input.to_vec();
     numbers.sort();
 
     // In this case we will get one index
@@ -21,11 +21,7 @@ fn median(input: &Vec<u32>) -> u32 {
 /* Calculate fuel consumption */
 
 fn get_steps(from: u32, to: u32) -> u32 {
-    if from > to {
-        from - to
-    } else {
-        to - from
-    }
+    from.abs_diff(to)
 }
 
 fn calc_trip(from: u32, to: u32) -> u32 {
@@ -33,46 +29,60 @@ fn calc_trip(from: u32, to: u32) -> u32 {
     (steps * (steps + 1)) / 2
 }
 
-fn calc_fuel<F: Fn(u32, u32) -> u32>(calculator: F, positions: &Vec<u32>, position: u32) -> u32 {
+fn calc_fuel<F: Fn(u32, u32) -> u32>(calculator: F, positions: &[u32], position: u32) -> u32 {
     positions.iter().map(|&x| calculator(position, x)).sum()
 }
 
 /* Solutions */
 
-fn part01(input: &Vec<u32>) -> u32 {
+fn part01(input: &[u32]) -> u32 {
     calc_fuel(get_steps, input, median(input))
 }
 
-fn part02(input: &Vec<u32>) -> u32 {
+fn part02(input: &[u32]) -> u32 {
     let fuel_cost = means(input).map(|x| calc_fuel(calc_trip, input, x));
     fuel_cost[0].min(fuel_cost[1])
 }
 
-pub fn day_07() -> Solution {
-    let input = read_comma_separated_number_input("./input/day_07.txt");
-    let timer = std::time::Instant::now();
-    Solution::new(7, part01(&input), part02(&input), timer.elapsed())
+pub struct Day07;
+
+impl Day for Day07 {
+    type Input = Vec<u32>;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(7, "The Treachery of Whales")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        to_comma_separated_numbers(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 /* Brute force solution - for fun */
 
 #[allow(dead_code)]
-fn brute<F: Fn(u32, u32) -> u32 + Copy>(calc: F, input: &Vec<u32>) -> u32 {
+fn brute<F: Fn(u32, u32) -> u32 + Copy>(calc: F, input: &[u32]) -> u32 {
     let min = *input.iter().min().unwrap();
     let max = *input.iter().max().unwrap() + 1;
 
-    (min..max)
-        .into_iter()
-        .fold(u32::MAX, |cost, x| cost.min(calc_fuel(calc, &input, x)))
+    (min..max).fold(u32::MAX, |cost, x| cost.min(calc_fuel(calc, input, x)))
 }
 
 #[allow(dead_code)]
-fn brute_01(input: &Vec<u32>) -> u32 {
+fn brute_01(input: &[u32]) -> u32 {
     brute(get_steps, input)
 }
 
 #[allow(dead_code)]
-fn brute_02(input: &Vec<u32>) -> u32 {
+fn brute_02(input: &[u32]) -> u32 {
     brute(calc_trip, input)
 }
 
@@ -80,25 +90,26 @@ fn brute_02(input: &Vec<u32>) -> u32 {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read_comma_separated_number_input;
 
     #[test]
     fn test_mean_uneven() {
-        assert_eq!(means(&vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14]), [4, 5])
+        assert_eq!(means(&[16, 1, 2, 0, 4, 2, 7, 1, 2, 14]), [4, 5])
     }
 
     #[test]
     fn test_mean_even() {
-        assert_eq!(means(&vec![1, 1, 1, 1]), [1, 1])
+        assert_eq!(means(&[1, 1, 1, 1]), [1, 1])
     }
 
     #[test]
     fn test_mean_empty() {
-        assert_eq!(means(&vec![]), [0, 0])
+        assert_eq!(means(&[]), [0, 0])
     }
 
     #[test]
     fn test_median() {
-        assert_eq!(median(&vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14]), 2)
+        assert_eq!(median(&[16, 1, 2, 0, 4, 2, 7, 1, 2, 14]), 2)
     }
 
     #[test]
@@ -113,35 +124,35 @@ mod tests {
 
     #[test]
     fn test_part01() {
-        assert_eq!(part01(&vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14]), 37)
+        assert_eq!(part01(&[16, 1, 2, 0, 4, 2, 7, 1, 2, 14]), 37)
     }
 
     #[test]
     fn test_part01_final() {
         let input = &read_comma_separated_number_input("./input/day_07.txt");
-        assert_eq!(part01(&input), 348664)
+        assert_eq!(part01(input), 348664)
     }
 
     #[test]
     fn test_brute_01() {
         let input = &read_comma_separated_number_input("./input/day_07.txt");
-        assert_eq!(brute_01(&input), 348664)
+        assert_eq!(brute_01(input), 348664)
     }
 
     #[test]
     fn test_part02() {
-        assert_eq!(part02(&vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14]), 168)
+        assert_eq!(part02(&[16, 1, 2, 0, 4, 2, 7, 1, 2, 14]), 168)
     }
 
     #[test]
     fn test_part02_final() {
         let input = &read_comma_separated_number_input("./input/day_07.txt");
-        assert_eq!(part02(&input), 100220525)
+        assert_eq!(part02(input), 100220525)
     }
 
     #[test]
     fn test_brute_02() {
         let input = &read_comma_separated_number_input("./input/day_07.txt");
-        assert_eq!(part02(&input), 100220525)
+        assert_eq!(part02(input), 100220525)
     }
 }
@@ -1,29 +1,21 @@
-use crate::{read_lines, Solution};
+use crate::{check_chars, to_lines, try_split_once, Day, Error, Metadata, Result};
 
 fn is_simple_digit(digit: &&str) -> bool {
-    match digit.len() {
-        2 | 3 | 4 | 7 => true,
-        _ => false,
-    }
+    matches!(digit.len(), 2 | 3 | 4 | 7)
 }
 
-fn count_simple_digits(segment: &String) -> usize {
+fn count_simple_digits(segment: &str) -> usize {
     let (_, digits) = segment.split_once("|").unwrap();
-    digits
-        .split(" ")
-        .into_iter()
-        .filter(is_simple_digit)
-        .count()
+    digits.split(" ").filter(is_simple_digit).count()
 }
 
 fn find_number_by_len(len: usize, signals: &str) -> Vec<&str> {
     signals
         .split(" ")
-        .into_iter()
         .find(|signal| signal.len() == len)
         .unwrap()
         .split("")
-        .filter(|&x| x.len() > 0)
+        .filter(|&x| !x.is_empty())
         .collect()
 }
 
@@ -31,17 +23,11 @@ fn parse_digit<'a>(signals: &str, digit: &str) -> Option<&'a str> {
     let one = find_number_by_len(2, signals);
     let four = find_number_by_len(4, signals);
 
-    let contains_one = digit.contains(one.get(0).unwrap()) && digit.contains(one.get(1).unwrap());
+    let contains_one = digit.contains(one.first().unwrap()) && digit.contains(one.get(1).unwrap());
 
     let matches_with_four = digit
         .split("")
-        .filter_map(|x| {
-            if x.len() > 0 && four.contains(&x) {
-                Some(x)
-            } else {
-                None
-            }
-        })
+        .filter(|x| !x.is_empty() && four.contains(x))
         .count();
 
     match digit.len() {
@@ -69,7 +55,7 @@ fn parse_digit<'a>(signals: &str, digit: &str) -> Option<&'a str> {
     }
 }
 
-fn parse_segment(segment: &String) -> u64 {
+fn parse_segment(segment: &str) -> u64 {
     let (signals, digits) = segment.split_once("|").unwrap();
 
     digits
@@ -81,20 +67,56 @@ fn parse_segment(segment: &String) -> u64 {
         .unwrap()
 }
 
+fn parse_entries(input: &str) -> Result<Vec<String>> {
+    for line in input.lines() {
+        check_chars(
+            line,
+            |c| ('a'..='g').contains(&c) || c == ' ' || c == '|',
+            "a segment a-g",
+        )?;
+
+        let (signals, digits) = try_split_once(line, " | ")?;
+        if signals.split(" ").count() != 10 {
+            return Err(Error::parse(signals, "10 signal patterns"));
+        }
+        if digits.split(" ").count() != 4 {
+            return Err(Error::parse(digits, "4 digits"));
+        }
+    }
+
+    Ok(to_lines(input))
+}
+
 /* Solutions */
 
-fn part01(input: &Vec<String>) -> usize {
-    input.into_iter().map(count_simple_digits).sum()
+fn part01(input: &[String]) -> usize {
+    input.iter().map(|line| count_simple_digits(line)).sum()
 }
 
-fn part02(input: &Vec<String>) -> u64 {
-    input.into_iter().map(parse_segment).sum()
+fn part02(input: &[String]) -> u64 {
+    input.iter().map(|line| parse_segment(line)).sum()
 }
 
-pub fn day_08() -> Solution {
-    let input = read_lines("./input/day_08.txt");
-    let timer = std::time::Instant::now();
-    Solution::new(8, part01(&input), part02(&input), timer.elapsed())
+pub struct Day08;
+
+impl Day for Day08 {
+    type Input = Vec<String>;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(8, "Seven Segment Search")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_entries(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 /* Tests */
@@ -103,6 +125,7 @@ pub fn day_08() -> Solution {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read_lines;
 
     #[test]
     fn test_parse_digit_input_cdfeb() {
@@ -251,7 +274,7 @@ mod tests {
 
     #[test]
     fn test_part01() {
-        assert_eq!(part01(&vec![
+        assert_eq!(part01(&[
             String::from("be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe"),
             String::from("edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc"),
             String::from("fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg"),
@@ -272,7 +295,7 @@ mod tests {
 
     #[test]
     fn test_part02() {
-        assert_eq!(part02(&vec![
+        assert_eq!(part02(&[
             String::from("be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe"),
             String::from("edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc"),
             String::from("fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg"),
@@ -1,121 +1,91 @@
 use std::collections::HashSet;
 
-use crate::{parse_number_string, read_lines, sort_desc, Solution};
+use crate::{sort_desc, Day, Error, Grid, Metadata, Result};
 
-#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
-struct Point(i16, i16);
-
-#[derive(Debug, PartialEq, Clone, Eq, Hash)]
-struct Adjacent {
-    value: u8,
-    values: Vec<(u8, Point)>,
-    position: Point,
+fn is_low_point(heightmap: &Grid<u8>, position: (usize, usize)) -> bool {
+    heightmap
+        .neighbours(position)
+        .all(|neighbour| heightmap[position] < heightmap[neighbour])
 }
 
-struct Matrix(Vec<Vec<u8>>);
-
-impl Matrix {
-    fn new(input: Vec<String>) -> Matrix {
-        Matrix(input.iter().map(parse_number_string).collect())
-    }
+fn low_points(heightmap: &Grid<u8>) -> Vec<(usize, usize)> {
+    heightmap
+        .positions()
+        .filter(|&position| is_low_point(heightmap, position))
+        .collect()
+}
 
-    fn get(&self, Point(x, y): Point) -> Option<(u8, Point)> {
-        if x < 0 || y < 0 {
-            None
-        } else {
-            self.0
-                .get(y as usize)
-                .and_then(|r| r.get(x as usize))
-                .map(|v| (*v, Point(x, y)))
+/* DFS */
+fn basin_size(heightmap: &Grid<u8>, low_point: (usize, usize)) -> u64 {
+    let mut stack = vec![low_point];
+    let mut discovered = HashSet::new();
+
+    while let Some(position) = stack.pop() {
+        if discovered.insert(position) {
+            heightmap
+                .neighbours(position)
+                .filter(|&n| heightmap[n] < 9 && heightmap[n] > heightmap[position])
+                .for_each(|n| stack.push(n));
         }
     }
 
-    fn find_adjacent(&self, Point(x, y): Point) -> Adjacent {
-        Adjacent {
-            value: self.get(Point(x, y)).unwrap().0,
-            values: vec![
-                self.get(Point(x - 1, y)),
-                self.get(Point(x + 1, y)),
-                self.get(Point(x, y - 1)),
-                self.get(Point(x, y + 1)),
-            ]
-            .iter()
-            .filter_map(|x| *x)
-            .collect(),
-            position: Point(x, y),
-        }
-    }
+    discovered.len() as u64
+}
 
-    fn to_adjacents(&self) -> Vec<Adjacent> {
-        let width = self.0.get(0).unwrap().len();
-        let len = self.0.len();
-
-        (0..len)
-            .into_iter()
-            .flat_map(|y| {
-                (0..width)
-                    .into_iter()
-                    .map(|x| self.find_adjacent(Point(x as i16, y as i16)))
-                    .collect::<Vec<Adjacent>>()
-            })
-            .collect()
-    }
+fn basin_sizes(heightmap: &Grid<u8>) -> Vec<u64> {
+    low_points(heightmap)
+        .into_iter()
+        .map(|low_point| basin_size(heightmap, low_point))
+        .collect()
+}
 
-    fn get_low_points(&self) -> Vec<Adjacent> {
-        self.to_adjacents()
-            .iter()
-            .filter(|adjacent| {
-                adjacent.value < adjacent.values.iter().map(|(v, _)| *v).min().unwrap()
-            })
-            .map(|a| a.to_owned())
-            .collect()
-    }
+fn parse_heightmap(input: &str) -> Result<Grid<u8>> {
+    let heightmap = Grid::parse_digits(input)?;
 
-    fn basin_sizes(&self) -> Vec<u64> {
-        self.get_low_points()
-            .iter()
-            .map(|low_point| self.basin_size(low_point.to_owned()))
-            .collect()
+    // Every low point is in its own basin, and part 2 needs three of them.
+    if low_points(&heightmap).len() < 3 {
+        return Err(Error::parse(
+            input,
+            "a heightmap with at least 3 low points",
+        ));
     }
 
-    /* DFS */
-    fn basin_size(&self, adjacent: Adjacent) -> u64 {
-        let mut stack = vec![adjacent];
-        let mut discovered = HashSet::new();
-
-        while !stack.is_empty() {
-            let a = stack.pop().unwrap();
-            if !discovered.contains(&a) {
-                discovered.insert(a.to_owned());
-                a.values
-                    .iter()
-                    .filter(|(v, _)| *v < 9 && v > &a.value)
-                    .for_each(|(_, point)| stack.push(self.find_adjacent(*point)));
-            }
-        }
-
-        discovered.len() as u64
-    }
+    Ok(heightmap)
 }
 
 /* Solutions */
 
-fn part01(input: &Matrix) -> u16 {
-    input
-        .get_low_points()
-        .iter()
-        .map(|a| (a.value + 1) as u16)
+fn part01(heightmap: &Grid<u8>) -> u16 {
+    low_points(heightmap)
+        .into_iter()
+        .map(|position| (heightmap[position] + 1) as u16)
         .sum()
 }
 
-fn part02(input: &Matrix) -> u64 {
-    sort_desc(input.basin_sizes())[..3].into_iter().product()
+fn part02(heightmap: &Grid<u8>) -> u64 {
+    sort_desc(basin_sizes(heightmap))[..3].iter().product()
 }
 
-pub fn day_09() -> Solution {
-    let input = Matrix::new(read_lines("./input/day_09.txt"));
-    let timer = std::time::Instant::now();
-    Solution::new(9, part01(&input), part02(&input), timer.elapsed())
+pub struct Day09;
+
+impl Day for Day09 {
+    type Input = Grid<u8>;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(9, "Smoke Basin")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_heightmap(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 /* Tests */
@@ -124,36 +94,39 @@ pub fn day_09() -> Solution {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read;
 
     #[test]
-    fn test_find_adjacent() {
-        let matrix = Matrix::new(vec![
-            String::from("2199943210"),
-            String::from("3987894921"),
-            String::from("9856789892"),
-            String::from("8767896789"),
-            String::from("9899965678"),
-        ]);
+    fn test_low_points() {
+        let heightmap =
+            Grid::parse_digits("2199943210\n3987894921\n9856789892\n8767896789\n9899965678")
+                .unwrap();
+
+        assert_eq!(low_points(&heightmap), vec![(1, 0), (9, 0), (2, 2), (6, 4)]);
+        assert_eq!(basin_size(&heightmap, (1, 0)), 3);
+        assert_eq!(basin_size(&heightmap, (2, 2)), 14);
+    }
 
+    #[test]
+    fn test_parse_too_few_basins() {
+        assert!(parse_heightmap("").is_err());
+        let error = parse_heightmap("1991\n9999").unwrap_err();
         assert_eq!(
-            matrix.find_adjacent(Point(1, 0)),
-            Adjacent {
-                value: 1,
-                values: vec![(2, Point(0, 0)), (9, Point(2, 0)), (9, Point(1, 1))],
-                position: Point(1, 0)
-            }
-        )
+            error.to_string(),
+            "expected a heightmap with at least 3 low points, found \"1991\\n9999\""
+        );
+        assert!(parse_heightmap("19191").is_ok());
     }
 
     #[test]
     fn test_part01() {
-        let input = Matrix::new(read_lines("./input/day_09.txt"));
+        let input = Grid::parse_digits(&read("./input/day_09.txt")).unwrap();
         assert_eq!(part01(&input), 572)
     }
 
     #[test]
     fn test_part02() {
-        let input = Matrix::new(read_lines("./input/day_09.txt"));
+        let input = Grid::parse_digits(&read("./input/day_09.txt")).unwrap();
         assert_eq!(part02(&input), 847044)
     }
 }
@@ -1,4 +1,4 @@
-use crate::{pipe, read_lines, sort_desc, Solution};
+use crate::{check_chars, pipe, sort_desc, to_lines, Day, Error, Metadata, Result};
 
 #[derive(Debug, PartialEq, Eq)]
 enum Char {
@@ -55,7 +55,7 @@ enum Line {
 }
 
 impl Line {
-    fn parse(line: &String) -> Line {
+    fn parse(line: &str) -> Line {
         let mut visited: Vec<Char> = vec![];
         let mut chars = line.chars();
         loop {
@@ -106,19 +106,47 @@ impl Line {
     }
 }
 
+fn parse_lines(input: &str) -> Result<Vec<String>> {
+    for line in input.lines() {
+        check_chars(line, |c| "()[]{}<>".contains(c), "a bracket")?;
+
+        let mut depth: usize = 0;
+        for (i, c) in line.char_indices() {
+            depth = match c {
+                '(' | '[' | '{' | '<' => depth + 1,
+                _ => depth
+                    .checked_sub(1)
+                    .ok_or_else(|| Error::parse(&line[i..=i], "an opening bracket"))?,
+            };
+        }
+    }
+
+    // Part 2 takes the middle score of the incomplete lines.
+    if !input
+        .lines()
+        .any(|line| matches!(Line::parse(line), Line::Incomplete(_)))
+    {
+        return Err(Error::parse(input, "at least one incomplete line"));
+    }
+
+    Ok(to_lines(input))
+}
+
 /* Solutions */
 
-fn part01(input: &Vec<String>) -> u64 {
+fn part01(input: &[String]) -> u64 {
     input
         .iter()
+        .map(String::as_str)
         .map(pipe(Line::parse, Line::as_checkers_points))
         .sum()
 }
 
-fn part02(input: &Vec<String>) -> u64 {
+fn part02(input: &[String]) -> u64 {
     let scores = sort_desc(
         input
             .iter()
+            .map(String::as_str)
             .filter_map(pipe(Line::parse, Line::to_autocomplete_points))
             .collect(),
     );
@@ -126,10 +154,26 @@ fn part02(input: &Vec<String>) -> u64 {
     *scores.get(scores.len() / 2).unwrap()
 }
 
-pub fn day_10() -> Solution {
-    let input = read_lines("./input/day_10.txt");
-    let timer = std::time::Instant::now();
-    Solution::new(10, part01(&input), part02(&input), timer.elapsed())
+pub struct Day10;
+
+impl Day for Day10 {
+    type Input = Vec<String>;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(10, "Syntax Scoring")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 /* Tests */
@@ -138,6 +182,7 @@ pub fn day_10() -> Solution {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read_lines;
 
     #[test]
     fn test_parse_line_ok() {
@@ -216,6 +261,19 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_parse_invalid_lines() {
+        let input = "[<>]\n<>)";
+        let error = parse_lines(input).unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected an opening bracket, found \")\""
+        );
+        assert!(parse_lines("").is_err());
+        assert!(parse_lines("[<>]\n(]").is_err());
+        assert_eq!(parse_lines("[<>]\n(]\n{(").unwrap().len(), 3);
+    }
+
     #[test]
     fn test_part01() {
         let input = read_lines("./input/day_10.txt");
@@ -1,174 +1,112 @@
 use std::collections::HashSet;
 
-use crate::{parse_number_string, read_lines, Solution};
+use crate::{Day, Error, Grid, Metadata, Result};
 
-#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
-struct Point(usize, usize);
-
-impl Point {
-    fn is_flashing(&self, grid: &Grid) -> bool {
-        grid.get(*self) > 9
-    }
-}
-
-#[derive(Debug, PartialEq, Clone, Copy)]
-struct Grid([[u8; 10]; 10]);
-
-impl Grid {
-    fn positions() -> [Point; 100] {
-        let mut pos = [Point(0, 0); 100];
-        let mut index = 0;
-
-        for y in 0..10 {
-            for x in 0..10 {
-                pos[index] = Point(x, y);
-                index += 1;
-            }
-        }
-
-        pos
-    }
-
-    fn new(input: Vec<String>) -> Grid {
-        let input: Vec<Vec<u8>> = input.iter().map(parse_number_string).collect();
-
-        Grid(
-            Grid::positions()
-                .iter()
-                .fold([[0; 10]; 10], |mut grid, &Point(x, y)| {
-                    grid[y][x] = *input.get(y).and_then(|r| r.get(x)).unwrap();
-                    grid
-                }),
-        )
-    }
-
-    fn set(&self, Point(x, y): Point, value: u8) -> Grid {
-        let mut grid = self.0;
-        grid[x][y] = value;
-        Grid(grid)
-    }
-
-    fn get(&self, Point(x, y): Point) -> u8 {
-        self.0[x][y]
-    }
-
-    fn increment_all(&self) -> Grid {
-        Grid(
-            Grid::positions()
-                .iter()
-                .fold([[0; 10]; 10], |mut grid, &Point(x, y)| {
-                    grid[y][x] = self.0[y][x] + 1;
-                    grid
-                }),
-        )
-    }
-
-    fn increment_adjacent(&self, Point(x, y): Point, has_flashed: &HashSet<Point>) -> Grid {
-        let x = x as i8;
-        let y = y as i8;
-        self.clone()
-            .increment_if_in_bound(has_flashed, x - 1, y - 1)
-            .increment_if_in_bound(has_flashed, x, y - 1)
-            .increment_if_in_bound(has_flashed, x + 1, y - 1)
-            .increment_if_in_bound(has_flashed, x - 1, y)
-            .increment_if_in_bound(has_flashed, x + 1, y)
-            .increment_if_in_bound(has_flashed, x - 1, y + 1)
-            .increment_if_in_bound(has_flashed, x, y + 1)
-            .increment_if_in_bound(has_flashed, x + 1, y + 1)
-    }
-
-    fn increment_if_in_bound(mut self, has_flashed: &HashSet<Point>, x: i8, y: i8) -> Grid {
-        if x >= 0 && x < 10 && y >= 0 && y < 10 {
-            let x = x as usize;
-            let y = y as usize;
-            if !has_flashed.contains(&Point(x, y)) {
-                self.0[x][y] += 1;
-            }
-        }
-
-        self
-    }
-}
-
-#[derive(Debug, PartialEq, Clone, Copy)]
-struct State {
-    grid: Grid,
-    flashes: u16,
-    step_flashes: u16,
+#[derive(Debug, PartialEq, Clone)]
+pub struct State {
+    grid: Grid<u8>,
+    flashes: usize,
+    step_flashes: usize,
 }
 
 impl State {
-    fn new(input: Vec<String>) -> State {
+    fn new(grid: Grid<u8>) -> State {
         State {
-            grid: Grid::new(input),
+            grid,
             flashes: 0,
             step_flashes: 0,
         }
     }
 
     fn step(&self) -> State {
-        let mut grid = self.grid.increment_all();
-        let mut flashes = 0;
-        let mut has_flashed: HashSet<Point> = HashSet::new();
-        let mut should_flash: Vec<Point> = vec![];
-
-        loop {
-            Grid::positions().iter().for_each(|&point| {
-                if point.is_flashing(&grid) && !has_flashed.contains(&point) {
-                    flashes += 1;
-                    should_flash.push(point);
-                    has_flashed.insert(point);
-                    grid = grid.set(point, 0);
+        let mut grid = self.grid.map(|energy| energy + 1);
+        let mut has_flashed: HashSet<(usize, usize)> = HashSet::new();
+        let mut should_flash: Vec<(usize, usize)> =
+            grid.positions().filter(|&p| grid[p] > 9).collect();
+
+        while let Some(position) = should_flash.pop() {
+            if !has_flashed.insert(position) {
+                continue;
+            }
+            let neighbours: Vec<(usize, usize)> = grid.neighbours_diagonal(position).collect();
+            for neighbour in neighbours {
+                grid[neighbour] += 1;
+                if grid[neighbour] > 9 && !has_flashed.contains(&neighbour) {
+                    should_flash.push(neighbour);
                 }
-            });
-
-            if should_flash.is_empty() {
-                break;
-            } else {
-                grid = should_flash.iter().fold(grid, |grid, point| {
-                    grid.increment_adjacent(*point, &has_flashed)
-                });
-                should_flash = vec![];
             }
         }
 
+        has_flashed.iter().for_each(|&position| grid[position] = 0);
+
         State {
             grid,
-            flashes: self.flashes + flashes,
-            step_flashes: flashes,
+            flashes: self.flashes + has_flashed.len(),
+            step_flashes: has_flashed.len(),
         }
     }
 }
 
+fn parse_state(input: &str) -> Result<State> {
+    let grid = Grid::parse_digits(input)?;
+
+    if grid.width() == 0 {
+        return Err(Error::parse(input, "a map of energy levels"));
+    }
+
+    Ok(State::new(grid))
+}
+
 /* Solutions */
 
-fn part01(input: &State) -> u16 {
-    let final_state = (0..100)
-        .into_iter()
-        .fold(input.clone(), |state, _| state.step());
+fn part01(input: &State) -> usize {
+    let final_state = (0..100).fold(input.clone(), |state, _| state.step());
 
     final_state.flashes
 }
 
-fn part02(input: &State) -> u16 {
-    let mut state = *input;
+/// The first step where all octopuses flash, or `None` if they never do.
+fn part02(input: &State) -> Option<usize> {
+    let mut state = input.clone();
+    // The steps only depend on the energy levels, so seeing them again means the octopuses cycle.
+    let mut seen: HashSet<Grid<u8>> = HashSet::new();
+
     let mut step = 0;
 
-    loop {
+    while seen.insert(state.grid.clone()) {
         step += 1;
         state = state.step();
-        if state.step_flashes == 100 {
-            break;
+        if state.step_flashes == state.grid.width() * state.grid.height() {
+            return Some(step);
         }
     }
 
-    step
+    None
 }
 
-pub fn day_11() -> Solution {
-    let input = State::new(read_lines("./input/day_11.txt"));
-    let timer = std::time::Instant::now();
-    Solution::new(11, part01(&input), part02(&input), timer.elapsed())
+pub struct Day11;
+
+impl Day for Day11 {
+    type Input = State;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(11, "Dumbo Octopus")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_state(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).map_or_else(
+            || String::from("never synchronizes"),
+            |step| step.to_string(),
+        )
+    }
 }
 
 /* Tests */
@@ -177,10 +115,20 @@ pub fn day_11() -> Solution {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read;
+
+    fn parse_rows<S: AsRef<str>>(rows: &[S]) -> Result<State> {
+        let rows: Vec<&str> = rows.iter().map(|row| row.as_ref()).collect();
+        parse_state(&rows.join("\n"))
+    }
+
+    fn grid(rows: [[u8; 10]; 10]) -> Grid<u8> {
+        Grid::from_rows(rows.iter().map(|row| row.to_vec()).collect())
+    }
 
     #[test]
     fn test_new_grid() {
-        let state = State::new(vec![
+        let state = parse_rows(&[
             String::from("5483143223"),
             String::from("2745854711"),
             String::from("5264556173"),
@@ -191,12 +139,13 @@ mod tests {
             String::from("6882881134"),
             String::from("4846848554"),
             String::from("5283751526"),
-        ]);
+        ])
+        .unwrap();
 
         assert_eq!(
             state,
             State {
-                grid: Grid([
+                grid: grid([
                     [5, 4, 8, 3, 1, 4, 3, 2, 2, 3],
                     [2, 7, 4, 5, 8, 5, 4, 7, 1, 1],
                     [5, 2, 6, 4, 5, 5, 6, 1, 7, 3],
@@ -216,7 +165,7 @@ mod tests {
 
     #[test]
     fn test_step_1() {
-        let state = State::new(vec![
+        let state = parse_rows(&[
             String::from("5483143223"),
             String::from("2745854711"),
             String::from("5264556173"),
@@ -228,12 +177,13 @@ mod tests {
             String::from("4846848554"),
             String::from("5283751526"),
         ])
+        .unwrap()
         .step();
 
         assert_eq!(
             state,
             State {
-                grid: Grid([
+                grid: grid([
                     [6, 5, 9, 4, 2, 5, 4, 3, 3, 4],
                     [3, 8, 5, 6, 9, 6, 5, 8, 2, 2],
                     [6, 3, 7, 5, 6, 6, 7, 2, 8, 4],
@@ -253,7 +203,7 @@ mod tests {
 
     #[test]
     fn test_step_2() {
-        let state = State::new(vec![
+        let state = parse_rows(&[
             String::from("5483143223"),
             String::from("2745854711"),
             String::from("5264556173"),
@@ -265,13 +215,14 @@ mod tests {
             String::from("4846848554"),
             String::from("5283751526"),
         ])
+        .unwrap()
         .step()
         .step();
 
         assert_eq!(
             state,
             State {
-                grid: Grid([
+                grid: grid([
                     [8, 8, 0, 7, 4, 7, 6, 5, 5, 5],
                     [5, 0, 8, 9, 0, 8, 7, 0, 5, 4],
                     [8, 5, 9, 7, 8, 8, 9, 6, 0, 8],
@@ -291,7 +242,7 @@ mod tests {
 
     #[test]
     fn test_step_10() {
-        let state = State::new(vec![
+        let state = parse_rows(&[
             String::from("5483143223"),
             String::from("2745854711"),
             String::from("5264556173"),
@@ -303,6 +254,7 @@ mod tests {
             String::from("4846848554"),
             String::from("5283751526"),
         ])
+        .unwrap()
         .step()
         .step()
         .step()
@@ -317,7 +269,7 @@ mod tests {
         assert_eq!(
             state,
             State {
-                grid: Grid([
+                grid: grid([
                     [0, 4, 8, 1, 1, 1, 2, 9, 7, 6],
                     [0, 0, 3, 1, 1, 1, 2, 0, 0, 9],
                     [0, 0, 4, 1, 1, 1, 2, 5, 0, 4],
@@ -334,15 +286,52 @@ mod tests {
             }
         )
     }
+    #[test]
+    fn test_any_size() {
+        let state = parse_rows(&["11111", "19991", "19191", "19991", "11111"]).unwrap();
+        assert_eq!(
+            state.step().grid,
+            Grid::parse_digits("34543\n40004\n50005\n40004\n34543").unwrap()
+        );
+        assert_eq!(state.step().flashes, 9);
+        assert!(parse_rows(&["123", "45"]).is_err());
+        assert!(parse_rows::<&str>(&[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_state() {
+        let input = "123\n4x6";
+        let error = parse_state(input).unwrap_err().locate(input);
+        assert!(error.to_string().starts_with("line 2, column 2: "));
+    }
+
+    #[test]
+    fn test_large_grid() {
+        let rows = vec!["9".repeat(100); 100];
+        assert_eq!(part01(&parse_rows(&rows).unwrap()), 100_000);
+    }
+
+    #[test]
+    fn test_never_synchronizes() {
+        assert_eq!(part02(&parse_rows(&["1", "9"]).unwrap()), None);
+    }
+
+    #[test]
+    fn test_example() {
+        let input = parse_state(&read("./input/examples/day_11.txt")).unwrap();
+        assert_eq!(part01(&input), 1656);
+        assert_eq!(part02(&input), Some(195));
+    }
+
     #[test]
     fn test_part01() {
-        let input = State::new(read_lines("./input/day_11.txt"));
+        let input = parse_state(&read("./input/day_11.txt")).unwrap();
         assert_eq!(part01(&input), 1613)
     }
 
     #[test]
     fn test_part02() {
-        let input = State::new(read_lines("./input/day_11.txt"));
-        assert_eq!(part02(&input), 510)
+        let input = parse_state(&read("./input/day_11.txt")).unwrap();
+        assert_eq!(part02(&input), Some(510))
     }
 }
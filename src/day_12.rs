@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::{is_upper, map_pair, read_lines, Solution};
+use crate::{is_upper, map_pair, try_split_once, Day, Metadata, Result};
 
 type CaveSystem = HashMap<Cave, Vec<Cave>>;
 
@@ -12,20 +12,20 @@ fn add_path(mut case_system: CaveSystem, from: &Cave, to: &Cave) -> CaveSystem {
     case_system
 }
 
-fn create_map(lines: Vec<String>) -> CaveSystem {
+fn create_map<S: AsRef<str>>(lines: &[S]) -> Result<CaveSystem> {
     let mut case_system = HashMap::new();
 
     for line in lines {
-        let (from, to) = map_pair(Cave::new, line.split_once("-").unwrap());
+        let (from, to) = map_pair(Cave::new, try_split_once(line.as_ref(), "-")?);
         case_system = add_path(case_system, &to, &from);
         case_system = add_path(case_system, &from, &to);
     }
 
-    case_system
+    Ok(case_system)
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
-enum Cave {
+pub enum Cave {
     Start,
     End,
     Big(String),
@@ -43,10 +43,7 @@ impl Cave {
     }
 
     fn is_small(&self) -> bool {
-        match self {
-            &Cave::Small(_) => true,
-            _ => false,
-        }
+        matches!(self, Cave::Small(_))
     }
 }
 
@@ -111,15 +108,14 @@ fn count_paths(allow_visit_again: bool, cave_system: &CaveSystem) -> usize {
     let mut stack = Vec::new();
     stack.push(Path::new(allow_visit_again));
 
-    while !stack.is_empty() {
-        let path = stack.pop().unwrap();
+    while let Some(path) = stack.pop() {
         let cave = path.current_cave();
 
         if Path::has_reached_end(cave) {
             paths.push(path);
         } else {
             for adjacent in cave_system.get(cave).unwrap_or(&vec![]) {
-                if Path::allows_adjacent(&path, &adjacent) {
+                if Path::allows_adjacent(&path, adjacent) {
                     stack.push(path.append(adjacent));
                 }
             }
@@ -139,10 +135,26 @@ fn part02(cave_system: &CaveSystem) -> usize {
     count_paths(true, cave_system)
 }
 
-pub fn day_12() -> Solution {
-    let input = create_map(read_lines("./input/day_12.txt"));
-    let timer = std::time::Instant::now();
-    Solution::new(12, part01(&input), part02(&input), timer.elapsed())
+pub struct Day12;
+
+impl Day for Day12 {
+    type Input = CaveSystem;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(12, "Passage Pathing")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        create_map(&input.lines().collect::<Vec<&str>>())
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 /* Tests */
@@ -151,17 +163,18 @@ pub fn day_12() -> Solution {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read_lines;
 
     #[test]
     fn test_part01() {
-        let input = create_map(read_lines("./input/day_12.txt"));
+        let input = create_map(&read_lines("./input/day_12.txt")).unwrap();
         assert_eq!(part01(&input), 4691)
     }
 
     #[test]
     #[ignore]
     fn test_part02() {
-        let input = create_map(read_lines("./input/day_12.txt"));
+        let input = create_map(&read_lines("./input/day_12.txt")).unwrap();
         assert_eq!(part02(&input), 140718)
     }
 }
@@ -4,8 +4,10 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use crate::{map_pair, map_snd, read, unsafe_parse, Solution};
+use crate::{map_pair, map_snd, try_parse, try_split_once, Day, Error, Metadata, Result};
 
+/// The letter with the hash of its dots, or `?` for shapes that aren't a known letter,
+/// like the square of the example.
 fn hash_to_char_str(hash: u64) -> &'static str {
     match hash {
         3947155009679962619 => "A",
@@ -21,7 +23,7 @@ fn hash_to_char_str(hash: u64) -> &'static str {
         14519949062532219633 => "P",
         5425530575798285626 => "R",
         6876405955879760556 => "Z",
-        _ => panic!("Not yet implemented: {}", hash),
+        _ => "?",
     }
 }
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -44,20 +46,21 @@ impl Coordinate {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-struct Paper {
+pub struct Paper {
     coordinates: HashSet<Coordinate>,
     width: u16,
     length: u16,
 }
 
 impl Paper {
-    fn new(input: &str) -> Paper {
+    fn new(input: &str) -> Result<Paper> {
         let mut width = 0;
         let mut length = 0;
         let mut coordinates = HashSet::new();
 
         for coordinate in input.lines() {
-            let (x, y) = map_pair(unsafe_parse, coordinate.split_once(",").unwrap());
+            let (x, y) = map_pair(try_parse, try_split_once(coordinate, ",")?);
+            let (x, y) = (x?, y?);
 
             width = width.max(x);
             length = length.max(y);
@@ -65,11 +68,11 @@ impl Paper {
             coordinates.insert(Coordinate::new(x, y));
         }
 
-        Paper {
+        Ok(Paper {
             coordinates,
             width,
             length,
-        }
+        })
     }
 
     fn fold(&self, instruction: &Instruction) -> Paper {
@@ -112,9 +115,9 @@ impl Paper {
             let mut letter = [[" "; 4]; 6];
             let mut hasher = std::collections::hash_map::DefaultHasher::new();
 
-            for y in 0..6 {
-                for x in 0..4 {
-                    letter[y][x] = if self
+            for (y, row) in letter.iter_mut().enumerate() {
+                for (x, pixel) in row.iter_mut().enumerate() {
+                    *pixel = if self
                         .coordinates
                         .contains(&Coordinate::new(x as u16 + offset, y as u16))
                     {
@@ -153,29 +156,32 @@ impl Display for Paper {
 }
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     FoldX(u16),
     FoldY(u16),
 }
 
 impl Instruction {
-    fn new(input: &str) -> Instruction {
-        let (_, line) = map_snd(unsafe_parse, input.split_once("=").unwrap());
+    fn new(input: &str) -> Result<Instruction> {
+        let (axis, line) = try_split_once(input, "=")?;
 
-        if input.contains("x") {
-            Instruction::FoldX(line)
-        } else {
-            Instruction::FoldY(line)
+        match axis {
+            "fold along x" => Ok(Instruction::FoldX(try_parse(line)?)),
+            "fold along y" => Ok(Instruction::FoldY(try_parse(line)?)),
+            _ => Err(Error::parse(axis, "fold along x or fold along y")),
         }
     }
 }
 
-fn parse(input: String) -> (Paper, Vec<Instruction>) {
-    let (coordinates, instruction) = input.split_once("\n\n").unwrap();
-    (
-        Paper::new(coordinates),
-        instruction.lines().map(Instruction::new).collect(),
-    )
+fn parse(input: &str) -> Result<(Paper, Vec<Instruction>)> {
+    let (coordinates, instruction) = try_split_once(input, "\n\n")?;
+    Ok((
+        Paper::new(coordinates)?,
+        instruction
+            .lines()
+            .map(Instruction::new)
+            .collect::<Result<_>>()?,
+    ))
 }
 
 /* Solutions */
@@ -191,10 +197,26 @@ fn part02((paper, instructions): &(Paper, Vec<Instruction>)) -> String {
         .to_letters()
 }
 
-pub fn day_13() -> Solution {
-    let input = parse(read("./input/day_13.txt"));
-    let timer = std::time::Instant::now();
-    Solution::new(13, part01(&input), part02(&input), timer.elapsed())
+pub struct Day13;
+
+impl Day for Day13 {
+    type Input = (Paper, Vec<Instruction>);
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(13, "Transparent Origami")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 /* Tests */
@@ -204,6 +226,7 @@ mod tests {
 
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read;
 
     #[test]
     fn test_fold_y() {
@@ -226,7 +249,8 @@ mod tests {
 2,14
 8,10
 9,0"#,
-        );
+        )
+        .unwrap();
         let mut expected_coordinates = HashSet::new();
 
         expected_coordinates.insert(Coordinate::new(0, 0));
@@ -277,7 +301,8 @@ mod tests {
 2,14
 8,10
 9,0"#,
-        );
+        )
+        .unwrap();
         let mut expected_coordinates = HashSet::new();
 
         expected_coordinates.insert(Coordinate::new(0, 0));
@@ -312,15 +337,21 @@ mod tests {
         assert_eq!(actual, expected)
     }
 
+    #[test]
+    fn test_unknown_letter() {
+        let input = parse(&read("./input/examples/day_13.txt")).unwrap();
+        assert_eq!(part02(&input), "?")
+    }
+
     #[test]
     fn test_part01() {
-        let input = parse(read("./input/day_13.txt"));
+        let input = parse(&read("./input/day_13.txt")).unwrap();
         assert_eq!(part01(&input), 675)
     }
 
     #[test]
     fn test_part02() {
-        let input = parse(read("./input/day_13.txt"));
+        let input = parse(&read("./input/day_13.txt")).unwrap();
         assert_eq!(part02(&input), "HZKHFEJZ")
     }
 }
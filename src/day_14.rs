@@ -1,22 +1,44 @@
 use std::{collections::HashMap, str::Chars};
 
-use crate::{map_both, map_pair, map_snd, read, Solution};
+use crate::{map_both, map_pair, try_split_once, Day, Error, Metadata, Result};
+
+type Rules = HashMap<(char, char), char>;
 
 fn pair(mut chars: Chars) -> (char, char) {
     (chars.next().unwrap(), chars.next().unwrap())
 }
 
-fn parse_insertion_rules(rules: &str) -> HashMap<(char, char), char> {
-    rules
-        .lines()
-        .map(|rule| {
-            map_both(
-                pair,
-                |mut cs| cs.next().unwrap(),
-                map_pair(|s| s.chars(), rule.split_once(" -> ").unwrap()),
-            )
-        })
-        .collect()
+fn parse_insertion_rule(rule: &str) -> Result<((char, char), char)> {
+    let (between, insertion) = try_split_once(rule, " -> ")?;
+
+    if between.chars().count() != 2 {
+        return Err(Error::parse(between, "a pair of elements"));
+    }
+    if insertion.chars().count() != 1 {
+        return Err(Error::parse(insertion, "a single element"));
+    }
+
+    Ok(map_both(
+        pair,
+        |mut cs| cs.next().unwrap(),
+        map_pair(|s| s.chars(), (between, insertion)),
+    ))
+}
+
+fn parse_polymer(input: &str) -> Result<(String, Rules)> {
+    let (template, rules) = try_split_once(input, "\n\n")?;
+
+    if template.is_empty() {
+        return Err(Error::parse(template, "a polymer template"));
+    }
+
+    Ok((
+        template.to_string(),
+        rules
+            .lines()
+            .map(parse_insertion_rule)
+            .collect::<Result<_>>()?,
+    ))
 }
 
 fn chunk(template: &str) -> HashMap<(char, char), usize> {
@@ -33,15 +55,12 @@ fn chunk(template: &str) -> HashMap<(char, char), usize> {
     chunks
 }
 
-fn step(input: &String, steps: usize) -> usize {
-    let (template, rules) = map_snd(parse_insertion_rules, input.split_once("\n\n").unwrap());
-
+fn step((template, rules): &(String, Rules), steps: usize) -> usize {
     // All characters except the first and last one are counted twice. Therefore I need to add one to both.
     let first = template.chars().next().unwrap();
     let last = template.chars().last().unwrap();
 
     let (min, max) = (0..steps)
-        .into_iter()
         .fold(chunk(template), |polymer_count, _| {
             let mut frequency = polymer_count.clone();
 
@@ -79,18 +98,34 @@ fn step(input: &String, steps: usize) -> usize {
 
 /* Solutions */
 
-fn part01(input: &String) -> usize {
+fn part01(input: &(String, Rules)) -> usize {
     step(input, 10)
 }
 
-fn part02(input: &String) -> usize {
+fn part02(input: &(String, Rules)) -> usize {
     step(input, 40)
 }
 
-pub fn day_14() -> Solution {
-    let input = read("./input/day_14.txt");
-    let timer = std::time::Instant::now();
-    Solution::new(14, part01(&input), part02(&input), timer.elapsed())
+pub struct Day14;
+
+impl Day for Day14 {
+    type Input = (String, Rules);
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(14, "Extended Polymerization")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_polymer(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 /* Tests */
@@ -99,16 +134,17 @@ pub fn day_14() -> Solution {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read;
 
     #[test]
     fn test_part01() {
-        let input = read("./input/day_14.txt");
+        let input = parse_polymer(&read("./input/day_14.txt")).unwrap();
         assert_eq!(part01(&input), 2657)
     }
 
     #[test]
     fn test_part02() {
-        let input = read("./input/day_14.txt");
+        let input = parse_polymer(&read("./input/day_14.txt")).unwrap();
         assert_eq!(part02(&input), 2911561572630)
     }
 }
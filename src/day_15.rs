@@ -1,104 +1,310 @@
-use crate::{parse_number_string, read_lines, Solution};
-use std::{cmp::Reverse, collections::BinaryHeap};
+use crate::{check_chars, Day, Error, Grid, Metadata, Result};
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fmt::{self, Display},
+};
 
-type Grid = [[u8; GRID_SIZE]; GRID_SIZE];
+fn parse_grid(input: &str) -> Result<Grid<u8>> {
+    for line in input.lines() {
+        check_chars(
+            line,
+            |c| ('1'..='9').contains(&c),
+            "a risk level from 1 to 9",
+        )?;
+    }
+    let grid = Grid::parse_digits(input)?;
 
-fn parse_grid(rows: &Vec<String>) -> Grid {
-    let mut grid = [[0u8; GRID_SIZE]; GRID_SIZE];
+    if grid.width() == 0 {
+        return Err(Error::parse(input, "a map of risk levels"));
+    }
 
-    rows.into_iter()
-        .map(parse_number_string)
-        .enumerate()
-        .for_each(|(y, row)| {
-            row.into_iter()
-                .enumerate()
-                .for_each(|(x, risk_level)| grid[y][x] = risk_level)
-        });
+    Ok(grid)
+}
 
-    grid
+/// A path through the risk map and its total risk. The risk of the first position isn't counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RiskPath {
+    pub risk: u64,
+    /// The positions from start to goal, both included.
+    pub positions: Vec<(usize, usize)>,
+    /// The number of positions the search expanded to find the path.
+    pub expanded: usize,
 }
 
-fn is_last<const N: usize>((x, y): (usize, usize)) -> bool {
-    x == N - 1 && y == N - 1
+impl RiskPath {
+    /// The risk map with the risk levels on the path in brackets, like `[1][1] 6`.
+    pub fn render(&self, map: &Grid<u8>) -> String {
+        let mut rendered = map.map(|risk| format!(" {} ", risk));
+        for &position in &self.positions {
+            rendered[position] = format!("[{}]", map[position]);
+        }
+        rendered
+            .rows()
+            .map(|row| row.concat().trim_end().to_string() + "\n")
+            .collect()
+    }
 }
 
-fn is_in_grid<const N: usize>((x, y): &&(usize, usize)) -> bool {
-    x < &&N && y < &&N
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Move up, down, left or right.
+    Four,
+    /// Move diagonally as well.
+    Eight,
 }
 
-fn adjacent((x, y): (usize, usize)) -> [(usize, usize); 4] {
-    [
-        // Make sure that the usize won't get an overflow
-        (x.wrapping_sub(1), y),
-        (x + 1, y),
-        (x, y.wrapping_sub(1)),
-        (x, y + 1),
-    ]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Heuristic {
+    /// Dijkstra's algorithm.
+    None,
+    /// A* with the least number of moves to the goal times the lowest risk level on the map.
+    Distance,
 }
 
-fn find_lowest_risk_level<const N: usize>(mut map: [[u8; N]; N]) -> u16 {
-    // Use Reverse to get the lowest instead of the highest risk.
-    let mut heap = BinaryHeap::from(vec![(Reverse(0), (0, 0))]);
+/// A search for the path with the lowest total risk between two positions of a risk map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pathfinder {
+    pub start: (usize, usize),
+    pub goal: (usize, usize),
+    pub connectivity: Connectivity,
+    pub heuristic: Heuristic,
+}
 
-    while let Some((Reverse(risk), position)) = heap.pop() {
-        if is_last::<N>(position) {
-            return risk;
+impl Pathfinder {
+    /// Search from the start to the goal over 4 neighbours without a heuristic.
+    pub fn new(start: (usize, usize), goal: (usize, usize)) -> Pathfinder {
+        Pathfinder {
+            start,
+            goal,
+            connectivity: Connectivity::Four,
+            heuristic: Heuristic::None,
         }
+    }
 
-        adjacent(position)
-            .iter()
-            // Remove wrapped positions
-            .filter(is_in_grid::<N>)
-            .for_each(|&(x, y)| {
-                if map[y][x] > 0 {
-                    heap.push((Reverse(risk + map[y][x] as u16), (x, y)));
-                    map[y][x] = 0; // "Mark as visited"
+    /// Search from the top left to the bottom right corner of the map.
+    pub fn corners(map: &Grid<u8>) -> Pathfinder {
+        let goal = (
+            map.width().saturating_sub(1),
+            map.height().saturating_sub(1),
+        );
+        Pathfinder::new((0, 0), goal)
+    }
+
+    fn neighbours<'a>(
+        &self,
+        map: &'a Grid<u8>,
+        position: (usize, usize),
+    ) -> Box<dyn Iterator<Item = (usize, usize)> + 'a> {
+        match self.connectivity {
+            Connectivity::Four => Box::new(map.neighbours(position)),
+            Connectivity::Eight => Box::new(map.neighbours_diagonal(position)),
+        }
+    }
+
+    /// The least number of moves from the position to the goal.
+    fn moves_to_goal(&self, (x, y): (usize, usize)) -> u64 {
+        let (dx, dy) = (x.abs_diff(self.goal.0), y.abs_diff(self.goal.1));
+        match self.connectivity {
+            Connectivity::Four => (dx + dy) as u64,
+            Connectivity::Eight => dx.max(dy) as u64,
+        }
+    }
+
+    /// The path with the lowest total risk, or `None` if the start or the goal is outside of the map.
+    pub fn find(&self, map: &Grid<u8>) -> Option<RiskPath> {
+        map.get(self.start)?;
+        map.get(self.goal)?;
+
+        let lowest_risk_level = match self.heuristic {
+            Heuristic::None => 0,
+            Heuristic::Distance => map.iter().map(|(_, &risk)| risk as u64).min().unwrap_or(0),
+        };
+        let estimate =
+            |risk: u64, position| risk + self.moves_to_goal(position) * lowest_risk_level;
+        let mut risks: Grid<Option<u64>> = map.map(|_| None);
+        let mut previous: Grid<Option<(usize, usize)>> = map.map(|_| None);
+        let mut expanded = 0;
+        // Use Reverse to get the lowest instead of the highest estimate.
+        let mut heap = BinaryHeap::from(vec![(Reverse(estimate(0, self.start)), 0, self.start)]);
+        risks[self.start] = Some(0);
+
+        while let Some((_, risk, position)) = heap.pop() {
+            // Skip positions that have been reached with a lower risk since they were pushed.
+            if risks[position].is_some_and(|lowest| lowest < risk) {
+                continue;
+            }
+            expanded += 1;
+            if position == self.goal {
+                return Some(RiskPath {
+                    risk,
+                    positions: trace_back(&previous, self.goal),
+                    expanded,
+                });
+            }
+
+            for neighbour in self.neighbours(map, position) {
+                let risk = risk + map[neighbour] as u64;
+                if risks[neighbour].is_none_or(|lowest| risk < lowest) {
+                    risks[neighbour] = Some(risk);
+                    previous[neighbour] = Some(position);
+                    heap.push((Reverse(estimate(risk, neighbour)), risk, neighbour));
                 }
-            })
+            }
+        }
+
+        None
     }
+}
 
-    0
+fn trace_back(
+    previous: &Grid<Option<(usize, usize)>>,
+    goal: (usize, usize),
+) -> Vec<(usize, usize)> {
+    let mut positions = vec![goal];
+    while let Some(position) = previous[*positions.last().unwrap()] {
+        positions.push(position);
+    }
+    positions.reverse();
+    positions
 }
 
-fn scale_grid<const N: usize>(grid: &Grid) -> [[u8; N]; N] {
-    let mut scaled_grid = [[0u8; N]; N];
-    let mut offset = 0;
+/// The path with the lowest total risk from the top left to the bottom right corner.
+pub fn lowest_risk_path(map: &Grid<u8>) -> Option<RiskPath> {
+    Pathfinder::corners(map).find(map)
+}
 
-    for y in 0..N {
-        if y != 0 && y % GRID_SIZE == 0 {
-            offset += 1;
-        }
-        for x in 0..N {
-            if x != 0 && x % GRID_SIZE == 0 {
-                offset += 1;
+fn find_lowest_risk_level(map: &Grid<u8>) -> u64 {
+    lowest_risk_path(map).map_or(0, |path| path.risk)
+}
+
+/// Why a map can't be tiled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TilingError {
+    ZeroFactor,
+    /// The risk levels must wrap around at 1 to 9 to stay single digits.
+    MaxRisk(u8),
+    /// A risk level of the map is 0 or above the max risk.
+    RiskLevel {
+        position: (usize, usize),
+        risk: u8,
+    },
+    TooLarge,
+}
+
+impl Display for TilingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TilingError::ZeroFactor => write!(f, "The tiling factor must be at least 1"),
+            TilingError::MaxRisk(max_risk) => {
+                write!(f, "The max risk must be 1 to 9, found {}", max_risk)
+            }
+            TilingError::RiskLevel { position, risk } => {
+                write!(f, "Risk level {} at {:?} is out of range", risk, position)
             }
-            // Add offset, - 1 to be able to use mod 9 and then + 1 to restore to correct value.
-            scaled_grid[y][x] = ((grid[y % GRID_SIZE][x % GRID_SIZE] + offset - 1) % 9) + 1;
+            TilingError::TooLarge => write!(f, "The tiled map is too large"),
         }
-        offset -= 4;
     }
+}
 
-    scaled_grid
+impl std::error::Error for TilingError {}
+
+/// Repeats a map `factor` times to the right and below. Each tile to the right or below adds 1 to
+/// the risk levels of the tile before it, and risk levels above the max risk wrap around to 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tiling {
+    factor: usize,
+    max_risk: u8,
 }
 
-/* Solutions */
+impl Tiling {
+    /// The tiling of the full map in part 2.
+    pub const PUZZLE: Tiling = Tiling {
+        factor: 5,
+        max_risk: 9,
+    };
+
+    pub fn new(factor: usize, max_risk: u8) -> std::result::Result<Tiling, TilingError> {
+        if factor == 0 {
+            return Err(TilingError::ZeroFactor);
+        }
+        if !(1..=9).contains(&max_risk) {
+            return Err(TilingError::MaxRisk(max_risk));
+        }
+
+        Ok(Tiling { factor, max_risk })
+    }
+
+    pub fn factor(&self) -> usize {
+        self.factor
+    }
+
+    pub fn max_risk(&self) -> u8 {
+        self.max_risk
+    }
+
+    /// Tile the map. Every risk level of the map must be between 1 and the max risk.
+    pub fn expand(&self, map: &Grid<u8>) -> std::result::Result<Grid<u8>, TilingError> {
+        if let Some((position, &risk)) = map
+            .iter()
+            .find(|&(_, &risk)| !(1..=self.max_risk).contains(&risk))
+        {
+            return Err(TilingError::RiskLevel { position, risk });
+        }
+
+        let (width, height) = (map.width(), map.height());
+        let size = width
+            .checked_mul(self.factor)
+            .zip(height.checked_mul(self.factor))
+            .filter(|(width, height)| width.checked_mul(*height).is_some());
+        let (tiled_width, tiled_height) = size.ok_or(TilingError::TooLarge)?;
+        let mut tiled = Grid::filled(tiled_width, tiled_height, 0);
+        let max_risk = self.max_risk as usize;
 
-// Default grid size.
-const GRID_SIZE: usize = 100;
+        for (x, y) in tiled.positions().collect::<Vec<_>>() {
+            // Each tile to the right or below adds 1.
+            let offset = x / width + y / height;
+            // - 1 to be able to use mod max risk and then + 1 to restore to correct value.
+            let risk = (map[(x % width, y % height)] as usize - 1 + offset) % max_risk + 1;
+            tiled[(x, y)] = risk as u8;
+        }
 
-fn part01(grid: &Grid) -> u16 {
-    find_lowest_risk_level(*grid)
+        Ok(tiled)
+    }
 }
 
-fn part02(grid: &Grid) -> u16 {
-    const N: usize = GRID_SIZE * 5;
-    find_lowest_risk_level(scale_grid::<N>(grid))
+/* Solutions */
+
+fn part01(grid: &Grid<u8>) -> u64 {
+    find_lowest_risk_level(grid)
 }
 
-pub fn day_15() -> Solution {
-    let input = parse_grid(&read_lines("./input/day_15.txt"));
-    let timer = std::time::Instant::now();
-    Solution::new(15, part01(&input), part02(&input), timer.elapsed())
+fn part02(grid: &Grid<u8>) -> u64 {
+    // The risk levels are checked to be 1 to 9 when parsing.
+    let tiled = Tiling::PUZZLE.expand(grid).unwrap();
+    find_lowest_risk_level(&tiled)
+}
+
+pub struct Day15;
+
+impl Day for Day15 {
+    type Input = Grid<u8>;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(15, "Chiton")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_grid(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 /* Tests */
@@ -107,16 +313,146 @@ pub fn day_15() -> Solution {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read;
+
+    #[test]
+    fn test_example() {
+        let input = parse_grid(&read("./input/examples/day_15.txt")).unwrap();
+        assert_eq!(part01(&input), 40);
+        assert_eq!(part02(&input), 315);
+    }
+
+    #[test]
+    fn test_rectangular_grid() {
+        let input = parse_grid("19\n11\n91").unwrap();
+        assert_eq!(part01(&input), 3);
+        assert_eq!(
+            Tiling::new(2, 9)
+                .unwrap()
+                .expand(&input)
+                .unwrap()
+                .to_string(),
+            "1921\n1122\n9112\n2132\n2233\n1223\n"
+        );
+        assert!(parse_grid("").is_err());
+        assert!(parse_grid("12\n3").is_err());
+        assert!(parse_grid("10\n11").is_err());
+    }
+
+    #[test]
+    fn test_tiling() {
+        let map = parse_grid("13\n21").unwrap();
+        let tiled = Tiling::new(3, 3).unwrap().expand(&map).unwrap();
+        assert_eq!(
+            tiled.to_string(),
+            "132132\n213213\n213213\n321321\n321321\n132132\n"
+        );
+
+        let tiled = Tiling::new(10, 9).unwrap().expand(&map).unwrap();
+        assert_eq!((tiled.width(), tiled.height()), (20, 20));
+        assert_eq!(tiled[(19, 19)], 1);
+        assert_eq!(Tiling::new(1, 9).unwrap().expand(&map), Ok(map.clone()));
+    }
+
+    #[test]
+    fn test_invalid_tiling() {
+        let map = parse_grid("13\n21").unwrap();
+        assert_eq!(Tiling::new(0, 9), Err(TilingError::ZeroFactor));
+        assert_eq!(Tiling::new(5, 10), Err(TilingError::MaxRisk(10)));
+        assert_eq!(
+            Tiling::new(5, 2).unwrap().expand(&map),
+            Err(TilingError::RiskLevel {
+                position: (1, 0),
+                risk: 3
+            })
+        );
+        assert_eq!(
+            Tiling::new(usize::MAX, 9).unwrap().expand(&map),
+            Err(TilingError::TooLarge)
+        );
+    }
+
+    #[test]
+    fn test_lowest_risk_path() {
+        let map = parse_grid(&read("./input/examples/day_15.txt")).unwrap();
+        let path = lowest_risk_path(&map).unwrap();
+
+        assert_eq!(path.positions.first(), Some(&(0, 0)));
+        assert_eq!(path.positions.last(), Some(&(9, 9)));
+        assert_eq!(
+            path.positions[1..]
+                .iter()
+                .map(|&p| map[p] as u64)
+                .sum::<u64>(),
+            path.risk
+        );
+        assert!(path
+            .positions
+            .windows(2)
+            .all(|w| map.neighbours(w[0]).any(|n| n == w[1])));
+    }
+
+    #[test]
+    fn test_render() {
+        let map = parse_grid("19\n11\n91").unwrap();
+        let path = lowest_risk_path(&map).unwrap();
+        assert_eq!(path.positions, vec![(0, 0), (0, 1), (1, 1), (1, 2)]);
+        assert_eq!(path.render(&map), "[1] 9\n[1][1]\n 9 [1]\n");
+    }
+
+    #[test]
+    fn test_large_tiling() {
+        // The tile offsets and the total risk don't fit in a u8 and a u16.
+        let tiled = Tiling::new(300, 9)
+            .unwrap()
+            .expand(&parse_grid("9").unwrap());
+        assert_eq!(tiled.unwrap()[(299, 299)], 4);
+        assert_eq!(find_lowest_risk_level(&Grid::filled(10000, 1, 9)), 89991);
+    }
+
+    #[test]
+    fn test_pathfinder() {
+        let map = parse_grid("1999\n9199\n9919\n9991").unwrap();
+        let mut pathfinder = Pathfinder::corners(&map);
+        assert_eq!(pathfinder.find(&map).unwrap().risk, 1 + 9 + 1 + 9 + 1 + 9);
+
+        pathfinder.connectivity = Connectivity::Eight;
+        let path = pathfinder.find(&map).unwrap();
+        assert_eq!(path.risk, 3);
+        assert_eq!(path.positions, vec![(0, 0), (1, 1), (2, 2), (3, 3)]);
+
+        let pathfinder = Pathfinder::new((3, 0), (1, 1));
+        assert_eq!(pathfinder.find(&map).unwrap().risk, 9 + 9 + 1);
+        assert!(Pathfinder::new((0, 0), (4, 0)).find(&map).is_none());
+    }
+
+    #[test]
+    fn test_a_star() {
+        let map = Tiling::PUZZLE
+            .expand(&parse_grid(&read("./input/examples/day_15.txt")).unwrap())
+            .unwrap();
+
+        for connectivity in [Connectivity::Four, Connectivity::Eight] {
+            let mut pathfinder = Pathfinder::corners(&map);
+            pathfinder.connectivity = connectivity;
+            let dijkstra = pathfinder.find(&map).unwrap();
+            pathfinder.heuristic = Heuristic::Distance;
+            let a_star = pathfinder.find(&map).unwrap();
+
+            assert_eq!(a_star.risk, dijkstra.risk);
+            assert!(a_star.expanded <= dijkstra.expanded);
+        }
+    }
 
     #[test]
     fn test_part01() {
-        let input = parse_grid(&read_lines("./input/day_15.txt"));
+        let input = parse_grid(&read("./input/day_15.txt")).unwrap();
         assert_eq!(part01(&input), 595)
     }
 
     #[test]
     fn test_part02() {
-        let input = parse_grid(&read_lines("./input/day_15.txt"));
+        let input = parse_grid(&read("./input/day_15.txt")).unwrap();
         assert_eq!(part02(&input), 2914)
     }
 }
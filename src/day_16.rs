@@ -1,4 +1,9 @@
-use crate::{read, Solution};
+use std::{
+    fmt::{self, Display},
+    iter::{self, Peekable},
+};
+
+use crate::{check_chars, try_parse, Day, Error, Metadata, Result};
 
 fn hex_to_binary(hex: &str) -> String {
     hex.chars()
@@ -7,108 +12,481 @@ fn hex_to_binary(hex: &str) -> String {
         .collect()
 }
 
-fn eval(op: &str, literals: Vec<u64>) -> u64 {
-    match op {
-        "000" => literals.iter().sum(),
-        "001" => literals.iter().product(),
-        "010" => *literals.iter().min().unwrap(),
-        "011" => *literals.iter().max().unwrap(),
-        "101" => (literals[0] > literals[1]) as u64,
-        "110" => (literals[0] < literals[1]) as u64,
-        "111" => (literals[0] == literals[1]) as u64,
-        _ => unreachable!(),
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeId {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    Literal,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+impl TypeId {
+    const ALL: [TypeId; 8] = [
+        TypeId::Sum,
+        TypeId::Product,
+        TypeId::Minimum,
+        TypeId::Maximum,
+        TypeId::Literal,
+        TypeId::GreaterThan,
+        TypeId::LessThan,
+        TypeId::EqualTo,
+    ];
+
+    /// The type ID of the 3 type ID bits of a packet.
+    pub fn from_bits(bits: u8) -> Option<TypeId> {
+        TypeId::ALL.get(bits as usize).copied()
+    }
+
+    pub fn bits(self) -> u8 {
+        self as u8
+    }
+
+    /// The name of the operator in expressions.
+    pub fn name(self) -> &'static str {
+        ["sum", "product", "min", "max", "literal", "gt", "lt", "eq"][self as usize]
+    }
+
+    /// Whether the operator compares exactly 2 operands.
+    fn is_comparison(self) -> bool {
+        matches!(
+            self,
+            TypeId::GreaterThan | TypeId::LessThan | TypeId::EqualTo
+        )
+    }
+
+    fn from_name(name: &str) -> Option<TypeId> {
+        TypeId::ALL
+            .iter()
+            .copied()
+            .find(|&type_id| type_id != TypeId::Literal && type_id.name() == name)
     }
 }
 
-fn parse_literal(binary: &str, mut pos: usize) -> (u64, usize) {
-    let mut value = String::new();
-    loop {
-        let bits = &binary[pos..pos + 5];
-        pos += 5;
-        value.push_str(&bits[1..]);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Contents {
+    Literal(u64),
+    Operator(Vec<Packet>),
+}
 
-        if bits.starts_with("0") {
-            return (u64::from_str_radix(&value, 2).unwrap(), pos);
+/// A BITS packet: a literal value, or an operator applied to its sub-packets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet {
+    pub version: u8,
+    pub type_id: TypeId,
+    pub contents: Contents,
+}
+
+impl Packet {
+    /// The packet and all of its sub-packets, depth first in the order of the transmission.
+    pub fn walk(&self) -> impl Iterator<Item = &Packet> {
+        let mut stack = vec![self];
+        iter::from_fn(move || {
+            let packet = stack.pop()?;
+            if let Contents::Operator(sub_packets) = &packet.contents {
+                stack.extend(sub_packets.iter().rev());
+            }
+            Some(packet)
+        })
+    }
+
+    /// The sum of the versions of the packet and all of its sub-packets.
+    pub fn version_sum(&self) -> u64 {
+        self.walk().map(|packet| packet.version as u64).sum()
+    }
+
+    /// The value of the expression, or `None` when the packet is malformed or the value overflows.
+    pub fn eval(&self) -> Option<u64> {
+        let sub_packets = match (&self.contents, self.type_id) {
+            (Contents::Literal(value), TypeId::Literal) => return Some(*value),
+            (Contents::Operator(sub_packets), type_id) if type_id != TypeId::Literal => sub_packets,
+            _ => return None,
+        };
+        let values = sub_packets
+            .iter()
+            .map(Packet::eval)
+            .collect::<Option<Vec<u64>>>()?;
+
+        match (self.type_id, &values[..]) {
+            (_, []) => None,
+            (TypeId::Sum, _) => values.iter().try_fold(0u64, |sum, &v| sum.checked_add(v)),
+            (TypeId::Product, _) => values
+                .iter()
+                .try_fold(1u64, |product, &v| product.checked_mul(v)),
+            (TypeId::Minimum, _) => values.iter().min().copied(),
+            (TypeId::Maximum, _) => values.iter().max().copied(),
+            (TypeId::GreaterThan, &[a, b]) => Some((a > b) as u64),
+            (TypeId::LessThan, &[a, b]) => Some((a < b) as u64),
+            (TypeId::EqualTo, &[a, b]) => Some((a == b) as u64),
+            _ => None,
         }
     }
+
+    /// Encode the packet as a hexadecimal transmission, padded with zeros to whole bytes.
+    /// Operators use the given length type, or the count of sub-packets when it fits in 11 bits
+    /// and the length in bits otherwise when the length type is `None`.
+    pub fn encode(&self, length_type: Option<LengthType>) -> Result<String, EncodeError> {
+        let mut binary = String::new();
+        self.write(&mut binary, length_type)?;
+        while !binary.len().is_multiple_of(8) {
+            binary.push('0');
+        }
+
+        Ok(binary
+            .as_bytes()
+            .chunks(4)
+            .map(|bits| {
+                let digit = bits
+                    .iter()
+                    .fold(0, |digit, &bit| digit << 1 | (bit - b'0') as u32);
+                std::char::from_digit(digit, 16)
+                    .unwrap()
+                    .to_ascii_uppercase()
+            })
+            .collect())
+    }
+
+    fn write(
+        &self,
+        binary: &mut String,
+        length_type: Option<LengthType>,
+    ) -> Result<(), EncodeError> {
+        if self.version > 7 {
+            return Err(EncodeError::Version(self.version));
+        }
+        write_bits(binary, self.version as u64, 3);
+        write_bits(binary, self.type_id.bits() as u64, 3);
+
+        match (&self.contents, self.type_id) {
+            (Contents::Literal(value), TypeId::Literal) => {
+                // Groups of 4 bits, each prefixed with 1 except the last one.
+                let groups = (64 - value.leading_zeros() as usize).div_ceil(4).max(1);
+                for group in (0..groups).rev() {
+                    binary.push(if group > 0 { '1' } else { '0' });
+                    write_bits(binary, value >> (4 * group) & 0b1111, 4);
+                }
+            }
+            (Contents::Operator(sub_packets), type_id) if type_id != TypeId::Literal => {
+                // Operators without operands and comparisons of other than 2 have no value.
+                if sub_packets.is_empty() || (type_id.is_comparison() && sub_packets.len() != 2) {
+                    return Err(EncodeError::Malformed);
+                }
+                let mut contents = String::new();
+                for sub_packet in sub_packets {
+                    sub_packet.write(&mut contents, length_type)?;
+                }
+                let fits_count = sub_packets.len() < 1 << 11;
+                let fits_bits = contents.len() < 1 << 15;
+
+                match length_type {
+                    Some(LengthType::Count) | None if fits_count => {
+                        binary.push('1');
+                        write_bits(binary, sub_packets.len() as u64, 11);
+                    }
+                    Some(LengthType::Bits) | None if fits_bits => {
+                        binary.push('0');
+                        write_bits(binary, contents.len() as u64, 15);
+                    }
+                    _ => return Err(EncodeError::TooLarge),
+                }
+                binary.push_str(&contents);
+            }
+            _ => return Err(EncodeError::Malformed),
+        }
+
+        Ok(())
+    }
 }
 
-fn parse_by_sub_count(binary: &str, mut pos: usize) -> (usize, u64, Vec<u64>) {
-    let num_sub_packets = usize::from_str_radix(&binary[pos + 1..pos + 12], 2).unwrap();
-    pos += 12;
-    let mut version = 0;
-    let results = (0..num_sub_packets)
-        .into_iter()
-        .map(|_| {
-            let (p, v, r) = parse(binary, pos);
-            pos = p;
-            version += v;
-            r
-        })
-        .collect();
+/// Expressions longer than this are split over multiple lines by `Packet::pretty`.
+const LINE_WIDTH: usize = 80;
+
+impl Packet {
+    /// The expression with the operands of operators that don't fit on a line indented below them.
+    pub fn pretty(&self) -> String {
+        let mut output = String::new();
+        self.write_pretty(&mut output, 0);
+        output
+    }
+
+    fn write_pretty(&self, output: &mut String, indent: usize) {
+        let line = self.to_string();
+
+        match &self.contents {
+            Contents::Operator(sub_packets) if 2 * indent + line.len() > LINE_WIDTH => {
+                output.push('(');
+                output.push_str(self.type_id.name());
+                for sub_packet in sub_packets {
+                    output.push('\n');
+                    output.push_str(&"  ".repeat(indent + 1));
+                    sub_packet.write_pretty(output, indent + 1);
+                }
+                output.push(')');
+            }
+            _ => output.push_str(&line),
+        }
+    }
+}
 
-    (pos, version, results)
+/// The packet as an expression like `(sum 1 (product 2 3))`. Versions aren't shown.
+impl Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.contents {
+            Contents::Literal(value) => write!(f, "{}", value),
+            Contents::Operator(sub_packets) => {
+                write!(f, "({}", self.type_id.name())?;
+                for sub_packet in sub_packets {
+                    write!(f, " {}", sub_packet)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
 }
 
-fn parse_by_bits(binary: &str, mut pos: usize) -> (usize, u64, Vec<u64>) {
-    let bits = usize::from_str_radix(&binary[pos + 1..pos + 16], 2).unwrap();
-    pos += 16;
-    let stop_at = pos + bits;
-    let mut version = 0;
-    let mut results = vec![];
+fn write_bits(binary: &mut String, value: u64, bits: usize) {
+    binary.push_str(&format!("{:0width$b}", value, width = bits));
+}
 
-    while pos < stop_at {
-        let (p, v, r) = parse(binary, pos);
-        pos = p;
-        version += v;
-        results.push(r);
+/// How an operator packet tells where its sub-packets end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthType {
+    /// Length type ID 0: the total length of the sub-packets in 15 bits.
+    Bits,
+    /// Length type ID 1: the number of sub-packets in 11 bits.
+    Count,
+}
+
+/// Why a packet can't be encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The version doesn't fit in 3 bits.
+    Version(u8),
+    /// A literal type ID with sub-packets, an operator type ID with a literal value,
+    /// an operator without sub-packets or a comparison without exactly 2 sub-packets.
+    Malformed,
+    /// The sub-packets of an operator don't fit in its length type.
+    TooLarge,
+}
+
+impl Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::Version(version) => write!(f, "Version {} doesn't fit in 3 bits", version),
+            EncodeError::Malformed => write!(f, "The contents don't match the type ID"),
+            EncodeError::TooLarge => write!(f, "The sub-packets don't fit in the length type"),
+        }
     }
+}
 
-    (pos, version, results)
+impl std::error::Error for EncodeError {}
+
+/// Reads the bits of a transmission from left to right.
+struct Reader<'a> {
+    hex: &'a str,
+    binary: String,
+    position: usize,
 }
 
-fn parse(binary: &str, mut pos: usize) -> (usize, u64, u64) {
-    // No more packet can be parsed
-    if pos + 6 > binary.len() {
-        return (binary.len(), 0, 0);
+impl Reader<'_> {
+    fn read(&mut self, bits: usize) -> Result<u64> {
+        let value = self
+            .binary
+            .get(self.position..self.position + bits)
+            .ok_or_else(|| Error::parse(self.hex, "a complete BITS transmission"))?;
+        self.position += bits;
+        Ok(u64::from_str_radix(value, 2).unwrap())
+    }
+
+    fn read_literal(&mut self) -> Result<u64> {
+        let mut value: u64 = 0;
+        loop {
+            let group = self.read(5)?;
+            if value.leading_zeros() < 4 {
+                return Err(Error::parse(self.hex, "a literal value of at most 64 bits"));
+            }
+            value = value << 4 | group & 0b1111;
+
+            if group & 0b10000 == 0 {
+                return Ok(value);
+            }
+        }
     }
-    let mut version = u64::from_str_radix(&binary[pos..pos + 3], 2).unwrap();
-    let type_id = &binary[pos + 3..pos + 6];
-    pos += 6;
 
-    let result = if type_id == "100" {
-        let (literal, p) = parse_literal(binary, pos);
-        pos = p;
-        literal
-    } else {
-        // operator
-        let (p, v, results) = if &binary[pos..pos + 1] == "1" {
-            parse_by_sub_count(binary, pos)
+    fn read_packet(&mut self) -> Result<Packet> {
+        let version = self.read(3)? as u8;
+        let type_id = TypeId::from_bits(self.read(3)? as u8).unwrap();
+
+        let contents = if type_id == TypeId::Literal {
+            Contents::Literal(self.read_literal()?)
+        } else if self.read(1)? == 1 {
+            let count = self.read(11)?;
+            Contents::Operator(
+                (0..count)
+                    .map(|_| self.read_packet())
+                    .collect::<Result<_>>()?,
+            )
         } else {
-            parse_by_bits(binary, pos)
+            let length = self.read(15)? as usize;
+            let end = self.position + length;
+            let mut sub_packets = vec![];
+            while self.position < end {
+                sub_packets.push(self.read_packet()?);
+            }
+            if self.position != end {
+                return Err(Error::parse(self.hex, "sub-packets that fill their length"));
+            }
+            Contents::Operator(sub_packets)
         };
 
-        pos = p;
-        version += v;
-        eval(type_id, results)
+        Ok(Packet {
+            version,
+            type_id,
+            contents,
+        })
+    }
+}
+
+/// Decode the outermost packet of a hexadecimal transmission. Trailing bits are ignored.
+pub fn parse_transmission(hex: &str) -> Result<Packet> {
+    check_chars(hex, |c| c.is_ascii_hexdigit(), "a hexadecimal digit")?;
+
+    Reader {
+        hex,
+        binary: hex_to_binary(hex),
+        position: 0,
+    }
+    .read_packet()
+}
+
+/// Split an expression into parentheses and the words between them.
+fn tokenize(input: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    let mut word_start = None;
+
+    for (i, c) in input.char_indices() {
+        if c == '(' || c == ')' || c.is_whitespace() {
+            if let Some(start) = word_start.take() {
+                tokens.push(&input[start..i]);
+            }
+            if !c.is_whitespace() {
+                tokens.push(&input[i..i + 1]);
+            }
+        } else if word_start.is_none() {
+            word_start = Some(i);
+        }
+    }
+    if let Some(start) = word_start {
+        tokens.push(&input[start..]);
+    }
+
+    tokens
+}
+
+fn parse_operand<'a, I>(tokens: &mut Peekable<I>, end: &'a str) -> Result<Packet>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let literal = |value| Packet {
+        version: 0,
+        type_id: TypeId::Literal,
+        contents: Contents::Literal(value),
     };
-    (pos, version, result)
+
+    match tokens.next() {
+        None => Err(Error::parse(end, "an expression")),
+        Some("(") => {
+            let name = tokens
+                .next()
+                .ok_or_else(|| Error::parse(end, "an operator"))?;
+            let type_id = TypeId::from_name(name)
+                .ok_or_else(|| Error::parse(name, "sum, product, min, max, gt, lt or eq"))?;
+            let mut operands = vec![];
+
+            while tokens.peek() != Some(&")") {
+                if tokens.peek().is_none() {
+                    return Err(Error::parse(end, ")"));
+                }
+                operands.push(parse_operand(tokens, end)?);
+            }
+            tokens.next();
+
+            if type_id.is_comparison() && operands.len() != 2 {
+                return Err(Error::parse(name, "a comparison of 2 operands"));
+            }
+            if operands.is_empty() {
+                return Err(Error::parse(name, "an operator with operands"));
+            }
+
+            Ok(Packet {
+                version: 0,
+                type_id,
+                contents: Contents::Operator(operands),
+            })
+        }
+        Some(token) if token == ")" => Err(Error::parse(token, "an expression")),
+        Some(number) => try_parse(number)
+            .map(literal)
+            .map_err(|_| Error::parse(number, "a number or (")),
+    }
+}
+
+/// Parse an expression like `(sum 1 (product 2 3) (max 4 5))` into packets with version 0.
+/// The operators are sum, product, min, max, gt, lt and eq.
+pub fn parse_expression(input: &str) -> Result<Packet> {
+    let end = &input[input.len()..];
+    let mut tokens = tokenize(input).into_iter().peekable();
+    let packet = parse_operand(&mut tokens, end)?;
+
+    match tokens.next() {
+        Some(token) => Err(Error::parse(token, "the end of the expression")),
+        None => Ok(packet),
+    }
+}
+
+/// Compile an expression to a hexadecimal BITS transmission.
+pub fn compile(expression: &str) -> Result<String> {
+    parse_expression(expression)?
+        .encode(None)
+        .map_err(|_| Error::parse(expression, "an expression that fits in a transmission"))
 }
 
 /* Solutions */
 
-fn part01(input: &String) -> u64 {
-    parse(&input, 0).1
+fn part01(packet: &Packet) -> u64 {
+    packet.version_sum()
 }
 
-fn part02(input: &String) -> u64 {
-    parse(&input, 0).2
+fn part02(packet: &Packet) -> Option<u64> {
+    packet.eval()
 }
 
-pub fn day_16() -> Solution {
-    let input = hex_to_binary(&read("./input/day_16.txt"));
-    let timer = std::time::Instant::now();
-    Solution::new(16, part01(&input), part02(&input), timer.elapsed())
+pub struct Day16;
+
+impl Day for Day16 {
+    type Input = Packet;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(16, "Packet Decoder")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_transmission(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).map_or_else(
+            || String::from("invalid expression"),
+            |value| value.to_string(),
+        )
+    }
 }
 
 /* Tests */
@@ -117,6 +495,7 @@ pub fn day_16() -> Solution {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read;
 
     #[test]
     fn test_hex_to_binary_d2fe28() {
@@ -134,75 +513,323 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_literal() {
+        assert_eq!(
+            parse_transmission("D2FE28").unwrap(),
+            Packet {
+                version: 6,
+                type_id: TypeId::Literal,
+                contents: Contents::Literal(2021),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_operator() {
+        let literal = |version, value| Packet {
+            version,
+            type_id: TypeId::Literal,
+            contents: Contents::Literal(value),
+        };
+
+        // Length type ID 0 with the length of the sub-packets in bits.
+        assert_eq!(
+            parse_transmission("38006F45291200").unwrap(),
+            Packet {
+                version: 1,
+                type_id: TypeId::LessThan,
+                contents: Contents::Operator(vec![literal(6, 10), literal(2, 20)]),
+            }
+        );
+        // Length type ID 1 with the number of sub-packets.
+        assert_eq!(
+            parse_transmission("EE00D40C823060").unwrap(),
+            Packet {
+                version: 7,
+                type_id: TypeId::Maximum,
+                contents: Contents::Operator(vec![literal(2, 1), literal(4, 2), literal(1, 3)]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_truncated_transmission() {
+        let input = "38006F4529";
+        let error = parse_transmission(input).unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected a complete BITS transmission, found \"38006F4529\""
+        );
+    }
+
+    #[test]
+    fn test_walk() {
+        let packet = parse_transmission("8A004A801A8002F478").unwrap();
+        let versions: Vec<u8> = packet.walk().map(|packet| packet.version).collect();
+        assert_eq!(versions, vec![4, 1, 5, 6]);
+        assert_eq!(
+            packet.walk().last().unwrap().contents,
+            Contents::Literal(15)
+        );
+    }
+
+    #[test]
+    fn test_eval_malformed_packet() {
+        let packet = Packet {
+            version: 0,
+            type_id: TypeId::GreaterThan,
+            contents: Contents::Operator(vec![]),
+        };
+        assert_eq!(packet.eval(), None);
+    }
+
+    #[test]
+    fn test_encode_literal() {
+        let packet = parse_transmission("D2FE28").unwrap();
+        assert_eq!(packet.encode(None), Ok(String::from("D2FE28")));
+    }
+
+    #[test]
+    fn test_encode_length_types() {
+        let packet = parse_transmission("38006F45291200").unwrap();
+        assert_eq!(
+            packet.encode(Some(LengthType::Bits)),
+            Ok(String::from("38006F45291200"))
+        );
+        let packet = parse_transmission("EE00D40C823060").unwrap();
+        assert_eq!(packet.encode(None), Ok(String::from("EE00D40C823060")));
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let packet = parse_transmission(&read("./input/day_16.txt")).unwrap();
+        for length_type in [None, Some(LengthType::Bits), Some(LengthType::Count)] {
+            let hex = packet.encode(length_type).unwrap();
+            assert_eq!(parse_transmission(&hex).unwrap(), packet);
+        }
+    }
+
+    #[test]
+    fn test_encode_invalid_packet() {
+        let literal = |version| Packet {
+            version,
+            type_id: TypeId::Literal,
+            contents: Contents::Literal(u64::MAX),
+        };
+        assert_eq!(literal(8).encode(None), Err(EncodeError::Version(8)));
+
+        let packet = Packet {
+            version: 0,
+            type_id: TypeId::Sum,
+            contents: Contents::Operator(vec![literal(0); 2048]),
+        };
+        assert_eq!(
+            packet.encode(Some(LengthType::Count)),
+            Err(EncodeError::TooLarge)
+        );
+        // 2048 literals of 16 groups don't fit in 15 bits either.
+        assert_eq!(packet.encode(None), Err(EncodeError::TooLarge));
+
+        let packet = Packet {
+            version: 0,
+            type_id: TypeId::Sum,
+            contents: Contents::Literal(1),
+        };
+        assert_eq!(packet.encode(None), Err(EncodeError::Malformed));
+    }
+
+    #[test]
+    fn test_encode_operator_without_operands() {
+        let packet = Packet {
+            version: 0,
+            type_id: TypeId::Sum,
+            contents: Contents::Operator(vec![]),
+        };
+        assert_eq!(packet.encode(None), Err(EncodeError::Malformed));
+    }
+
+    #[test]
+    fn test_encode_comparison_operands() {
+        let comparison = |operands: usize| Packet {
+            version: 0,
+            type_id: TypeId::LessThan,
+            contents: Contents::Operator(vec![parse_expression("1").unwrap(); operands]),
+        };
+        assert_eq!(comparison(1).encode(None), Err(EncodeError::Malformed));
+        assert_eq!(comparison(3).encode(None), Err(EncodeError::Malformed));
+        assert!(comparison(2).encode(None).is_ok());
+    }
+
+    #[test]
+    fn test_compile() {
+        let hex = compile("(sum 1 (product 2 3) (max 4 5))").unwrap();
+        let packet = parse_transmission(&hex).unwrap();
+        assert_eq!(packet.eval(), Some(12));
+        assert_eq!(packet.to_string(), "(sum 1 (product 2 3) (max 4 5))");
+        assert_eq!(
+            compile(" (gt\n  (min 3 7)  2)").unwrap(),
+            parse_expression("(gt (min 3 7) 2)")
+                .unwrap()
+                .encode(None)
+                .unwrap()
+        );
+        assert_eq!(parse_expression("42").unwrap().eval(), Some(42));
+    }
+
+    #[test]
+    fn test_display_transmission() {
+        let packet = parse_transmission("9C0141080250320F1802104A08").unwrap();
+        assert_eq!(packet.to_string(), "(eq (sum 1 3) (product 2 2))");
+        assert_eq!(
+            parse_expression(&packet.to_string()).unwrap().eval(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_pretty() {
+        let packet = parse_transmission("C200B40A82").unwrap();
+        assert_eq!(packet.pretty(), "(sum 1 2)");
+
+        let operand = format!("(product {})", ["1000000"; 8].join(" "));
+        let packet = parse_expression(&format!("(max 1 {})", operand)).unwrap();
+        assert_eq!(packet.pretty(), format!("(max\n  1\n  {})", operand));
+    }
+
+    #[test]
+    fn test_parse_invalid_expression() {
+        let error = |input: &str| {
+            parse_expression(input)
+                .unwrap_err()
+                .locate(input)
+                .to_string()
+        };
+
+        assert_eq!(
+            error("(sum 1 (pow 2 3))"),
+            "line 1, column 9: expected sum, product, min, max, gt, lt or eq, found \"pow\""
+        );
+        assert_eq!(
+            error("(gt 1)"),
+            "line 1, column 2: expected a comparison of 2 operands, found \"gt\""
+        );
+        assert_eq!(
+            error("(sum 1 x)"),
+            "line 1, column 8: expected a number or (, found \"x\""
+        );
+        assert_eq!(error("(sum 1"), "line 1, column 7: expected ), found \"\"");
+        assert_eq!(
+            error("(sum 1) 2"),
+            "line 1, column 9: expected the end of the expression, found \"2\""
+        );
+        assert_eq!(
+            error("  )"),
+            "line 1, column 3: expected an expression, found \")\""
+        );
+        assert!(parse_expression("").is_err());
+        assert!(parse_expression("(min)").is_err());
+    }
+
     #[test]
     fn test_part_1_8a004a801a8002f478() {
-        assert_eq!(part01(&hex_to_binary("8A004A801A8002F478")), 16)
+        assert_eq!(
+            part01(&parse_transmission("8A004A801A8002F478").unwrap()),
+            16
+        )
     }
 
     #[test]
     fn test_part_1_620080001611562c8802118e34() {
-        assert_eq!(part01(&hex_to_binary("620080001611562C8802118E34")), 12)
+        assert_eq!(
+            part01(&parse_transmission("620080001611562C8802118E34").unwrap()),
+            12
+        )
     }
 
     #[test]
     fn test_part_1_c0015000016115a2e0802f182340() {
-        assert_eq!(part01(&hex_to_binary("C0015000016115A2E0802F182340")), 23)
+        assert_eq!(
+            part01(&parse_transmission("C0015000016115A2E0802F182340").unwrap()),
+            23
+        )
     }
 
     #[test]
     fn test_part_1_a0016c880162017c3686b18a3d4780() {
-        assert_eq!(part01(&hex_to_binary("A0016C880162017C3686B18A3D4780")), 31)
+        assert_eq!(
+            part01(&parse_transmission("A0016C880162017C3686B18A3D4780").unwrap()),
+            31
+        )
     }
 
     #[test]
     fn test_part_2_sum() {
-        assert_eq!(part02(&hex_to_binary("C200B40A82")), 3)
+        assert_eq!(part02(&parse_transmission("C200B40A82").unwrap()), Some(3))
     }
 
     #[test]
     fn test_part_2_product() {
-        assert_eq!(part02(&hex_to_binary("04005AC33890")), 54)
+        assert_eq!(
+            part02(&parse_transmission("04005AC33890").unwrap()),
+            Some(54)
+        )
     }
 
     #[test]
     fn test_part_2_min() {
-        assert_eq!(part02(&hex_to_binary("880086C3E88112")), 7)
+        assert_eq!(
+            part02(&parse_transmission("880086C3E88112").unwrap()),
+            Some(7)
+        )
     }
 
     #[test]
     fn test_part_2_max() {
-        assert_eq!(part02(&hex_to_binary("CE00C43D881120")), 9)
+        assert_eq!(
+            part02(&parse_transmission("CE00C43D881120").unwrap()),
+            Some(9)
+        )
     }
 
     #[test]
     fn test_part_2_lt() {
-        assert_eq!(part02(&hex_to_binary("D8005AC2A8F0")), 1)
+        assert_eq!(
+            part02(&parse_transmission("D8005AC2A8F0").unwrap()),
+            Some(1)
+        )
     }
 
     #[test]
     fn test_part_2_gt() {
-        assert_eq!(part02(&hex_to_binary("F600BC2D8F")), 0)
+        assert_eq!(part02(&parse_transmission("F600BC2D8F").unwrap()), Some(0))
     }
 
     #[test]
     fn test_part_2_eq() {
-        assert_eq!(part02(&hex_to_binary("9C005AC2F8F0")), 0)
+        assert_eq!(
+            part02(&parse_transmission("9C005AC2F8F0").unwrap()),
+            Some(0)
+        )
     }
 
     #[test]
     fn test_part_2_sum_prod_eq() {
-        assert_eq!(part02(&hex_to_binary("9C0141080250320F1802104A08")), 1)
+        assert_eq!(
+            part02(&parse_transmission("9C0141080250320F1802104A08").unwrap()),
+            Some(1)
+        )
     }
 
     #[test]
     fn test_part01() {
-        let input = &hex_to_binary(&read("./input/day_16.txt"));
+        let input = parse_transmission(&read("./input/day_16.txt")).unwrap();
         assert_eq!(part01(&input), 886)
     }
 
     #[test]
     fn test_part02() {
-        let input = &hex_to_binary(&read("./input/day_16.txt"));
-        assert_eq!(part02(&input), 184487454837)
+        let input = parse_transmission(&read("./input/day_16.txt")).unwrap();
+        assert_eq!(part02(&input), Some(184487454837))
     }
 }
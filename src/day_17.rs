@@ -1,4 +1,4 @@
-use crate::{map_pair, read, unsafe_parse, Solution};
+use crate::{map_pair, try_parse, try_split_once, Day, Error, Metadata, Result};
 
 const LEFT: usize = 0;
 const RIGHT: usize = 1;
@@ -7,22 +7,26 @@ const BOTTOM: usize = 3;
 
 type TargetArea = [i16; 4];
 
-fn range_to_numbers(range: &str) -> (i16, i16) {
-    map_pair(unsafe_parse, range.split_once("..").unwrap())
+fn range_to_numbers(range: &str) -> Result<(i16, i16)> {
+    let (from, to) = map_pair(try_parse, try_split_once(range, "..")?);
+    Ok((from?, to?))
 }
 
-fn to_target_area(input: &str) -> TargetArea {
+fn to_target_area(input: &str) -> Result<TargetArea> {
     let mut target_area = [0; 4];
 
-    let ((left, right), (bottom, top)) =
-        map_pair(range_to_numbers, input[15..].split_once(", y=").unwrap());
+    let ranges = input
+        .strip_prefix("target area: x=")
+        .ok_or_else(|| Error::parse(input, "\"target area: x=\""))?;
+    let (x, y) = map_pair(range_to_numbers, try_split_once(ranges, ", y=")?);
+    let ((left, right), (bottom, top)) = (x?, y?);
 
     target_area[LEFT] = left;
     target_area[RIGHT] = right;
     target_area[TOP] = top;
     target_area[BOTTOM] = bottom;
 
-    target_area
+    Ok(target_area)
 }
 
 fn is_in(target: TargetArea, x: i16, y: i16) -> bool {
@@ -78,10 +82,26 @@ fn part02(target: &TargetArea) -> i16 {
     results.into_iter().sum()
 }
 
-pub fn day_17() -> Solution {
-    let input = to_target_area(&read("./input/day_17.txt"));
-    let timer = std::time::Instant::now();
-    Solution::new(17, part01(&input), part02(&input), timer.elapsed())
+pub struct Day17;
+
+impl Day for Day17 {
+    type Input = TargetArea;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(17, "Trick Shot")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        to_target_area(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 /* Tests */
@@ -90,11 +110,12 @@ pub fn day_17() -> Solution {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read;
 
     #[test]
     fn test_to_target_area_example() {
         assert_eq!(
-            to_target_area("target area: x=20..30, y=-10..-5"),
+            to_target_area("target area: x=20..30, y=-10..-5").unwrap(),
             [20, 30, -5, -10]
         )
     }
@@ -106,45 +127,45 @@ mod tests {
 
     #[test]
     fn test_simulate_6_3() {
-        let target = to_target_area("target area: x=20..30, y=-10..-5");
+        let target = to_target_area("target area: x=20..30, y=-10..-5").unwrap();
         assert_eq!(simulate(target, 6, 3), 1)
     }
 
     #[test]
     fn test_simulate_9_0() {
-        let target = to_target_area("target area: x=20..30, y=-10..-5");
+        let target = to_target_area("target area: x=20..30, y=-10..-5").unwrap();
         assert_eq!(simulate(target, 9, 0), 1)
     }
 
     #[test]
     fn test_simulate_7_2() {
-        let target = to_target_area("target area: x=20..30, y=-10..-5");
+        let target = to_target_area("target area: x=20..30, y=-10..-5").unwrap();
         assert_eq!(simulate(target, 7, 2), 1)
     }
 
     #[test]
     fn test_simulate_miss() {
-        let target = to_target_area("target area: x=20..30, y=-10..-5");
+        let target = to_target_area("target area: x=20..30, y=-10..-5").unwrap();
         assert_eq!(simulate(target, 0, 2), 0)
     }
 
     #[test]
     fn test_to_target_area() {
         assert_eq!(
-            to_target_area("target area: x=94..151, y=-156..-103"),
+            to_target_area("target area: x=94..151, y=-156..-103").unwrap(),
             [94, 151, -103, -156]
         )
     }
 
     #[test]
     fn test_part01() {
-        let input = to_target_area(&read("./input/day_17.txt"));
+        let input = to_target_area(&read("./input/day_17.txt")).unwrap();
         assert_eq!(part01(&input), 12090)
     }
 
     #[test]
     fn test_part02() {
-        let input = to_target_area(&read("./input/day_17.txt"));
+        let input = to_target_area(&read("./input/day_17.txt")).unwrap();
         assert_eq!(part02(&input), 5059)
     }
 }
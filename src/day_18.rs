@@ -1,28 +1,45 @@
-use crate::{read, Solution};
+use crate::{Day, Error, Metadata, Result};
 use std::fmt::Debug;
 
 #[derive(PartialEq, Eq, Clone)]
-enum Number {
+pub enum Number {
     Natural(u8),
     Pair(Box<Number>, Box<Number>),
 }
 
 impl Number {
-    fn from_str(input: &str) -> Number {
-        Number::parse(&mut input.chars())
-    }
-
-    fn parse(input: &mut impl Iterator<Item = char>) -> Number {
-        match input.next() {
-            Some('[') => {
-                let left = Number::parse(input);
-                input.next(); // remove ","
-                let right = Number::parse(input);
-                input.next(); // remove "]"
-                Number::pair(left, right)
+    fn from_str(input: &str) -> Result<Number> {
+        let mut chars = input.char_indices();
+        let number = Number::parse(input, &mut chars)?;
+
+        match chars.next() {
+            Some((i, _)) => Err(Error::parse(&input[i..], "end of line")),
+            None => Ok(number),
+        }
+    }
+
+    fn parse(input: &str, chars: &mut impl Iterator<Item = (usize, char)>) -> Result<Number> {
+        match chars.next() {
+            Some((_, '[')) => {
+                let left = Number::parse(input, chars)?;
+                Number::skip(input, chars, ',')?;
+                let right = Number::parse(input, chars)?;
+                Number::skip(input, chars, ']')?;
+                Ok(Number::pair(left, right))
             }
-            Some(v) => Number::Natural(v.to_digit(10).unwrap() as u8),
-            _ => unreachable!(),
+            Some((i, v)) => v
+                .to_digit(10)
+                .map(|n| Number::Natural(n as u8))
+                .ok_or_else(|| Error::parse(&input[i..i + v.len_utf8()], "a digit or [")),
+            None => Err(Error::parse(&input[input.len()..], "a digit or [")),
+        }
+    }
+
+    fn skip(input: &str, chars: &mut impl Iterator<Item = (usize, char)>, c: char) -> Result<()> {
+        match chars.next() {
+            Some((_, v)) if v == c => Ok(()),
+            Some((i, v)) => Err(Error::parse(&input[i..i + v.len_utf8()], &c.to_string())),
+            None => Err(Error::parse(&input[input.len()..], &c.to_string())),
         }
     }
 
@@ -90,7 +107,7 @@ impl Number {
     fn split(&mut self) -> bool {
         match self {
             Number::Natural(n) if *n > 9 => {
-                *self = Number::pair(Number::Natural(*n / 2), Number::Natural((*n + 1) / 2));
+                *self = Number::pair(Number::Natural(*n / 2), Number::Natural(n.div_ceil(2)));
                 true
             }
             Number::Pair(left, right) => left.split() || right.split(),
@@ -123,22 +140,30 @@ impl Debug for Number {
 
 /* Solutions */
 
-fn part01(input: &String) -> u16 {
+fn parse_numbers(input: &str) -> Result<Vec<Number>> {
+    if input.trim().is_empty() {
+        return Err(Error::parse(input, "a snailfish number"));
+    }
+
+    input.lines().map(Number::from_str).collect()
+}
+
+fn part01(input: &[Number]) -> u16 {
     input
-        .lines()
-        .map(Number::from_str)
+        .iter()
+        .cloned()
         .reduce(Number::add)
         .unwrap()
         .magnitude()
 }
 
-fn part02(input: &String) -> u16 {
-    let ns = input.lines().map(Number::from_str).collect::<Vec<_>>();
+fn part02(ns: &[Number]) -> u16 {
     let mut max = 0;
 
+    // Addition isn't commutative, so both orders of each pair are tried.
     for i in 0..ns.len() {
-        for j in i + 1..ns.len() {
-            if ns[i] != ns[j] {
+        for j in 0..ns.len() {
+            if i != j {
                 max = max.max(Number::add(ns[i].clone(), ns[j].clone()).magnitude());
             }
         }
@@ -147,10 +172,26 @@ fn part02(input: &String) -> u16 {
     max
 }
 
-pub fn day_18() -> Solution {
-    let input = read("./input/day_18.txt");
-    let timer = std::time::Instant::now();
-    Solution::new(18, part01(&input), part02(&input), timer.elapsed())
+pub struct Day18;
+
+impl Day for Day18 {
+    type Input = Vec<Number>;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(18, "Snailfish")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_numbers(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 /* Tests */
@@ -159,11 +200,12 @@ pub fn day_18() -> Solution {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read;
 
     #[test]
     fn test_parse_pair() {
         assert_eq!(
-            Number::parse(&mut "[1,2]".chars()),
+            Number::from_str("[1,2]").unwrap(),
             Number::Pair(Box::new(Number::Natural(1)), Box::new(Number::Natural(2)))
         )
     }
@@ -171,7 +213,7 @@ mod tests {
     #[test]
     fn test_parse_nested_pair() {
         assert_eq!(
-            Number::parse(&mut "[[1,2],3]".chars()),
+            Number::from_str("[[1,2],3]").unwrap(),
             Number::Pair(
                 Box::new(Number::Pair(
                     Box::new(Number::Natural(1)),
@@ -184,71 +226,102 @@ mod tests {
 
     #[test]
     fn test_add() {
-        let n1 = Number::from_str("[[[[4,3],4],4],[7,[[8,4],9]]]");
-        let n2 = Number::from_str("[1,1]");
+        let n1 = Number::from_str("[[[[4,3],4],4],[7,[[8,4],9]]]").unwrap();
+        let n2 = Number::from_str("[1,1]").unwrap();
 
         assert_eq!(
             Number::add(n1, n2),
-            Number::from_str("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]")
+            Number::from_str("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").unwrap()
         )
     }
 
     #[test]
     fn test_explode_left() {
-        let mut number = Number::from_str("[[[[[9,8],1],2],3],4]");
+        let mut number = Number::from_str("[[[[[9,8],1],2],3],4]").unwrap();
         let exploded = number.explode();
         assert_eq!(
             (exploded, number),
-            (true, Number::from_str("[[[[0,9],2],3],4]"))
+            (true, Number::from_str("[[[[0,9],2],3],4]").unwrap())
         )
     }
 
     #[test]
     fn test_explode_right() {
-        let mut result = Number::from_str("[7,[6,[5,[4,[3,2]]]]]");
+        let mut result = Number::from_str("[7,[6,[5,[4,[3,2]]]]]").unwrap();
         result.explode();
-        assert_eq!(result, Number::from_str("[7,[6,[5,[7,0]]]]"))
+        assert_eq!(result, Number::from_str("[7,[6,[5,[7,0]]]]").unwrap())
     }
     #[test]
     fn test_explode_nested() {
-        let mut result = Number::from_str("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]");
+        let mut result = Number::from_str("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]").unwrap();
         let exploded = result.explode();
         assert_eq!(
             (exploded, result),
-            (true, Number::from_str("[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]"))
+            (
+                true,
+                Number::from_str("[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]").unwrap()
+            )
         )
     }
 
     #[test]
     fn test_not_explode() {
-        let mut result = Number::from_str("[4,[3,2]]");
+        let mut result = Number::from_str("[4,[3,2]]").unwrap();
         let exploded = result.explode();
-        assert_eq!((exploded, result), (false, Number::from_str("[4,[3,2]]")))
+        assert_eq!(
+            (exploded, result),
+            (false, Number::from_str("[4,[3,2]]").unwrap())
+        )
     }
 
     #[test]
     fn test_not_explode_nested() {
         let mut result =
-            Number::from_str("[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]");
+            Number::from_str("[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]")
+                .unwrap();
         let exploded = result.explode();
         assert_eq!(
             (exploded, result),
             (
                 false,
                 Number::from_str("[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]")
+                    .unwrap()
             )
         )
     }
 
+    #[test]
+    fn test_parse_empty_input() {
+        assert_eq!(
+            parse_numbers("").unwrap_err().to_string(),
+            "expected a snailfish number, found \"\""
+        );
+        assert_eq!(part01(&parse_numbers("[1,2]").unwrap()), 7);
+    }
+
+    #[test]
+    fn test_part02_orders_and_duplicates() {
+        let a = Number::from_str("[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]").unwrap();
+        let b = Number::from_str("[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]").unwrap();
+        // Only a + b has the largest magnitude, so the later number has to come first.
+        assert!(Number::add(b.clone(), a.clone()).magnitude() < 3993);
+        assert_eq!(part02(&[b, a.clone()]), 3993);
+        // Equal numbers on different lines can be added as well.
+        assert_eq!(
+            part02(&[a.clone(), a.clone()]),
+            Number::add(a.clone(), a).magnitude()
+        );
+    }
+
     #[test]
     fn test_part01() {
-        let input = read("./input/day_18.txt");
+        let input = parse_numbers(&read("./input/day_18.txt")).unwrap();
         assert_eq!(part01(&input), 4243)
     }
 
     #[test]
     fn test_part02() {
-        let input = read("./input/day_18.txt");
+        let input = parse_numbers(&read("./input/day_18.txt")).unwrap();
         assert_eq!(part02(&input), 4701)
     }
 }
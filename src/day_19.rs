@@ -1,19 +1,351 @@
-use crate::{read, Solution};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display},
+};
+
+use crate::{try_parse, Day, Error, Metadata, Point3, Result};
+
+/// The number of beacons two scanners need to have in common to be aligned in the puzzle.
+pub const SCANNER_OVERLAP: usize = 12;
+
+/// A rotation matrix, applied as `rotation * point`.
+pub type Rotation = [[i64; 3]; 3];
+
+fn add(a: Point3, b: Point3) -> Point3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: Point3, b: Point3) -> Point3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: Point3, b: Point3) -> i64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Point3, b: Point3) -> Point3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn rotate(rotation: &Rotation, point: Point3) -> Point3 {
+    rotation.map(|row| dot(row, point))
+}
+
+/// The 24 ways a scanner can be facing. Each is a rotation matrix with one ±1 per row and column.
+pub fn scanner_rotations() -> Vec<Rotation> {
+    let mut rotations = vec![];
+
+    for x_axis in 0..3 {
+        for y_axis in (0..3).filter(|&axis| axis != x_axis) {
+            for (x_sign, y_sign) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+                let mut rotation = [[0; 3]; 3];
+                rotation[0][x_axis] = x_sign;
+                rotation[1][y_axis] = y_sign;
+                // The cross product of the first two rows keeps it a rotation and not a mirroring.
+                rotation[2] = cross(rotation[0], rotation[1]);
+                rotations.push(rotation);
+            }
+        }
+    }
+
+    rotations
+}
+
+/// Maps a point seen by one scanner to the coordinates of another.
+/// The translation is the position of the scanner in the other scanner's coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Transform {
+    pub rotation: Rotation,
+    pub translation: Point3,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        rotation: [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+        translation: [0, 0, 0],
+    };
+
+    pub fn apply(&self, point: Point3) -> Point3 {
+        add(rotate(&self.rotation, point), self.translation)
+    }
+
+    /// The transform in the opposite direction. The inverse of a rotation matrix is its transpose.
+    pub fn inverse(&self) -> Transform {
+        let rotation = [0, 1, 2].map(|row| self.rotation.map(|column| column[row]));
+
+        Transform {
+            rotation,
+            translation: rotate(&rotation, self.translation).map(|d| -d),
+        }
+    }
+
+    /// The transform that first applies `other` and then `self`.
+    pub fn then(&self, other: &Transform) -> Transform {
+        let columns = [0, 1, 2].map(|i| rotate(&self.rotation, other.rotation.map(|row| row[i])));
+
+        Transform {
+            rotation: [0, 1, 2].map(|row| columns.map(|column| column[row])),
+            translation: self.apply(other.translation),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scanner {
+    beacons: Vec<Point3>,
+    /// Squared distances between all pairs of beacons, which don't depend on how the scanner is facing.
+    fingerprint: HashMap<i64, usize>,
+}
+
+impl Scanner {
+    /// A scanner report with the beacons in the scanner's own coordinates.
+    pub fn new(beacons: Vec<Point3>) -> Scanner {
+        let mut fingerprint = HashMap::new();
+
+        for (i, a) in beacons.iter().enumerate() {
+            for b in &beacons[i + 1..] {
+                let d = sub(*a, *b);
+                *fingerprint.entry(dot(d, d)).or_insert(0) += 1;
+            }
+        }
+
+        Scanner {
+            beacons,
+            fingerprint,
+        }
+    }
+
+    pub fn beacons(&self) -> &[Point3] {
+        &self.beacons
+    }
+
+    /// Scanners with enough beacons in common must share the distances between those beacons.
+    fn may_overlap(&self, other: &Scanner, overlap: usize) -> bool {
+        let shared: usize = self
+            .fingerprint
+            .iter()
+            .map(|(d, count)| other.fingerprint.get(d).map_or(0, |c| *c.min(count)))
+            .sum();
+
+        shared >= overlap * (overlap - 1) / 2
+    }
+
+    /// Find the transform from the coordinates of `other` to the coordinates of this scanner.
+    fn align(&self, other: &Scanner, rotations: &[Rotation], overlap: usize) -> Option<Transform> {
+        if !self.may_overlap(other, overlap) {
+            return None;
+        }
+
+        for rotation in rotations {
+            let mut translations = HashMap::new();
+
+            for b in &other.beacons {
+                let rotated = rotate(rotation, *b);
+
+                for a in &self.beacons {
+                    let count = translations.entry(sub(*a, rotated)).or_insert(0);
+                    *count += 1;
+
+                    if *count >= overlap {
+                        return Some(Transform {
+                            rotation: *rotation,
+                            translation: sub(*a, rotated),
+                        });
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+fn parse_beacon(line: &str) -> Result<Point3> {
+    let coordinates: Vec<&str> = line.split(',').collect();
+
+    if coordinates.len() != 3 {
+        return Err(Error::parse(line, "x,y,z"));
+    }
+
+    Ok([
+        try_parse(coordinates[0])?,
+        try_parse(coordinates[1])?,
+        try_parse(coordinates[2])?,
+    ])
+}
+
+fn parse_scanners(input: &str) -> Result<Vec<Scanner>> {
+    input
+        .split("\n\n")
+        .map(|report| {
+            let mut lines = report.lines();
+            let header = lines.next().unwrap_or(report);
+
+            if !(header.starts_with("--- scanner ") && header.ends_with(" ---")) {
+                return Err(Error::parse(header, "--- scanner N ---"));
+            }
+
+            Ok(Scanner::new(
+                lines.map(parse_beacon).collect::<Result<_>>()?,
+            ))
+        })
+        .collect()
+}
+
+/// The scanners that don't have enough beacons in common with any scanner linked to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnlinkedScanners {
+    /// Indexes of the scanners in the order they were given.
+    pub scanners: Vec<usize>,
+}
+
+impl Display for UnlinkedScanners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scanners: Vec<String> = self.scanners.iter().map(usize::to_string).collect();
+        write!(
+            f,
+            "Scanners {} could not be linked to scanner 0",
+            scanners.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnlinkedScanners {}
+
+/// The scanners placed in the coordinates of the first scanner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registration {
+    /// The transform from each scanner to the first scanner, in the order the scanners were given.
+    pub transforms: Vec<Transform>,
+    /// All beacons without duplicates, sorted.
+    pub beacons: Vec<Point3>,
+}
+
+impl Registration {
+    /// The position of each scanner.
+    pub fn positions(&self) -> Vec<Point3> {
+        self.transforms.iter().map(|t| t.translation).collect()
+    }
+}
+
+/// Place every scanner in the coordinates of the first scanner. Two scanners are linked
+/// when at least `overlap` beacons match, and every scanner must be linked to the first,
+/// directly or through other scanners.
+pub fn register_scanners(
+    scanners: &[Scanner],
+    overlap: usize,
+) -> std::result::Result<Registration, UnlinkedScanners> {
+    let overlap = overlap.max(1);
+    let rotations = scanner_rotations();
+    let mut transforms: Vec<Option<Transform>> = vec![None; scanners.len()];
+    let mut queue = vec![0];
+    let mut tried = HashSet::new();
+
+    if let Some(first) = transforms.first_mut() {
+        *first = Some(Transform::IDENTITY);
+    }
+
+    // Align the unknown scanners with each located scanner once.
+    while let Some(i) = queue.pop() {
+        for j in 0..scanners.len() {
+            if transforms[j].is_some() || !tried.insert((i, j)) {
+                continue;
+            }
+
+            if let Some(transform) = scanners[i].align(&scanners[j], &rotations, overlap) {
+                transforms[j] = transforms[i].map(|to_first| to_first.then(&transform));
+                queue.push(j);
+            }
+        }
+    }
+
+    let unlinked: Vec<usize> = (0..scanners.len())
+        .filter(|&i| transforms[i].is_none())
+        .collect();
+    if !unlinked.is_empty() {
+        return Err(UnlinkedScanners { scanners: unlinked });
+    }
+
+    let transforms: Vec<Transform> = transforms.into_iter().flatten().collect();
+    let mut beacons: Vec<Point3> = scanners
+        .iter()
+        .zip(&transforms)
+        .flat_map(|(scanner, transform)| scanner.beacons.iter().map(move |b| transform.apply(*b)))
+        .collect::<HashSet<Point3>>()
+        .into_iter()
+        .collect();
+    beacons.sort_unstable();
+
+    Ok(Registration {
+        transforms,
+        beacons,
+    })
+}
+
+/// Parse the scanner reports and register them with the overlap of the puzzle.
+fn parse_registration(input: &str) -> Result<Registration> {
+    let scanners = parse_scanners(input)?;
+
+    register_scanners(&scanners, SCANNER_OVERLAP).map_err(|unlinked| {
+        // Point at the header of the first scanner that could not be linked.
+        let report = input
+            .split("\n\n")
+            .nth(unlinked.scanners[0])
+            .unwrap_or(input);
+        Error::parse(
+            report.lines().next().unwrap_or(report),
+            &format!(
+                "a scanner with {} beacons in common with scanner 0 or a linked scanner",
+                SCANNER_OVERLAP
+            ),
+        )
+    })
+}
+
+fn manhattan_distance(a: Point3, b: Point3) -> i64 {
+    sub(a, b).iter().map(|d| d.abs()).sum()
+}
 
 /* Solutions */
 
-fn part01(_input: &String) -> &str {
-    "NA"
+fn part01(registration: &Registration) -> usize {
+    registration.beacons.len()
 }
 
-fn part02(_input: &String) -> &str {
-    "NA"
+fn part02(registration: &Registration) -> i64 {
+    let positions = registration.positions();
+
+    positions
+        .iter()
+        .flat_map(|a| positions.iter().map(move |b| manhattan_distance(*a, *b)))
+        .max()
+        .unwrap_or(0)
 }
 
-pub fn day_19() -> Solution {
-    let input = read("./input/day_19.txt");
-    let timer = std::time::Instant::now();
-    Solution::new(19, part01(&input), part02(&input), timer.elapsed())
+pub struct Day19;
+
+impl Day for Day19 {
+    type Input = Registration;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(19, "Beacon Scanner")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_registration(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 /* Tests */
@@ -22,16 +354,184 @@ pub fn day_19() -> Solution {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read;
+
+    #[test]
+    fn test_rotations_are_unique() {
+        let rotations = scanner_rotations();
+        let unique: HashSet<Rotation> = rotations.iter().copied().collect();
+        assert_eq!(unique.len(), 24);
+        assert!(rotations.contains(&Transform::IDENTITY.rotation));
+    }
+
+    #[test]
+    fn test_rotations_keep_handedness() {
+        for rotation in scanner_rotations() {
+            assert_eq!(cross(rotation[0], rotation[1]), rotation[2]);
+            assert_eq!(
+                rotate(&rotation, cross([1, 0, 0], [0, 1, 0])),
+                cross(rotate(&rotation, [1, 0, 0]), rotate(&rotation, [0, 1, 0]))
+            );
+        }
+    }
+
+    #[test]
+    fn test_transform_then() {
+        let rotations = scanner_rotations();
+        let a = Transform {
+            rotation: rotations[5],
+            translation: [1, 2, 3],
+        };
+        let b = Transform {
+            rotation: rotations[17],
+            translation: [-40, 7, 11],
+        };
+        let point = [5, -8, 13];
+        assert_eq!(a.then(&b).apply(point), a.apply(b.apply(point)))
+    }
+
+    #[test]
+    fn test_parse_invalid_scanner() {
+        let input = "--- scanner 0 ---\n1,2,3\n4,5";
+        let error = parse_scanners(input).unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected x,y,z, found \"4,5\""
+        )
+    }
+
+    #[test]
+    fn test_parse_empty_input() {
+        let error = parse_scanners("").unwrap_err();
+        assert_eq!(error.to_string(), "expected --- scanner N ---, found \"\"")
+    }
+
+    #[test]
+    fn test_parse_unlinked_scanners() {
+        let input = "--- scanner 0 ---\n1,2,3\n\n--- scanner 1 ---\n4,5,6";
+        let error = parse_registration(input).unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 1: expected a scanner with 12 beacons in common with scanner 0 or a linked scanner, found \"--- scanner 1 ---\""
+        )
+    }
+
+    #[test]
+    fn test_register_example() {
+        let scanners = parse_scanners(&read("./input/examples/day_19.txt")).unwrap();
+        let registration = register_scanners(&scanners, SCANNER_OVERLAP).unwrap();
+        assert_eq!(
+            registration.positions(),
+            vec![
+                [0, 0, 0],
+                [68, -1246, -43],
+                [1105, -1205, 1229],
+                [-92, -2380, -20],
+                [-20, -1133, 1061]
+            ]
+        );
+        assert_eq!(registration.beacons.len(), 79);
+        assert!(registration.beacons.contains(&[-892, 524, 684]));
+    }
+
+    #[test]
+    fn test_transform_inverse() {
+        let transform = Transform {
+            rotation: scanner_rotations()[9],
+            translation: [68, -1246, -43],
+        };
+        let point = [686, 422, 578];
+        assert_eq!(transform.inverse().apply(transform.apply(point)), point);
+        assert_eq!(transform.then(&transform.inverse()), Transform::IDENTITY);
+    }
+
+    /// A deterministic sequence of numbers in -1000..1000 to generate beacons.
+    fn numbers(seed: u64) -> impl Iterator<Item = i64> {
+        let mut state = seed;
+        std::iter::repeat_with(move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) % 2000) as i64 - 1000
+        })
+    }
+
+    #[test]
+    fn test_register_generated_scanners() {
+        let rotations = scanner_rotations();
+        let expected: Vec<Transform> = (0..4)
+            .map(|i| Transform {
+                rotation: rotations[i * 7 % 24],
+                translation: [i as i64 * 500, i as i64 * -300, 0],
+            })
+            .collect();
+
+        // Every scanner sees the same 8 beacons around the origin and 10 beacons of its own.
+        let mut numbers = numbers(19);
+        let mut point = || [0, 1, 2].map(|_| numbers.next().unwrap());
+        let shared: Vec<Point3> = (0..8).map(|_| point()).collect();
+        let scanners: Vec<Scanner> = expected
+            .iter()
+            .map(|transform| {
+                let to_scanner = transform.inverse();
+                let own = (0..10).map(|_| point());
+                Scanner::new(
+                    shared
+                        .iter()
+                        .copied()
+                        .chain(own)
+                        .map(|p| to_scanner.apply(p))
+                        .collect(),
+                )
+            })
+            .collect();
+
+        let registration = register_scanners(&scanners, 8).unwrap();
+        assert_eq!(registration.transforms, expected);
+        assert_eq!(registration.beacons.len(), 8 + 4 * 10);
+        assert_eq!(
+            register_scanners(&scanners, 9).unwrap_err().to_string(),
+            "Scanners 1, 2, 3 could not be linked to scanner 0"
+        );
+    }
+
+    #[test]
+    fn test_register_far_beacons() {
+        let beacons = vec![[100_000, -100_000, 100_000], [-100_000, 100_000, 0]];
+        let scanners = [Scanner::new(beacons.clone()), Scanner::new(beacons)];
+        let registration = register_scanners(&scanners, 2).unwrap();
+        assert_eq!(registration.positions(), vec![[0, 0, 0], [0, 0, 0]]);
+    }
+
+    #[test]
+    fn test_register_no_scanners() {
+        assert!(register_scanners(&[], SCANNER_OVERLAP)
+            .unwrap()
+            .beacons
+            .is_empty())
+    }
+
+    #[test]
+    fn test_part01_example() {
+        let input = parse_registration(&read("./input/examples/day_19.txt")).unwrap();
+        assert_eq!(part01(&input), 79)
+    }
+
+    #[test]
+    fn test_part02_example() {
+        let input = parse_registration(&read("./input/examples/day_19.txt")).unwrap();
+        assert_eq!(part02(&input), 3621)
+    }
 
     #[test]
     fn test_part01() {
-        let input = read("./input/day_19.txt");
-        assert_eq!(part01(&input), "NA")
+        let input = parse_registration(&read("./input/day_19.txt")).unwrap();
+        assert_eq!(part01(&input), 440)
     }
 
     #[test]
     fn test_part02() {
-        let input = read("./input/day_19.txt");
-        assert_eq!(part02(&input), "NA")
+        let input = parse_registration(&read("./input/day_19.txt")).unwrap();
+        assert_eq!(part02(&input), 9167)
     }
 }
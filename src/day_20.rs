@@ -1,61 +1,52 @@
-use crate::{map_fst, read, Solution};
+use crate::{check_chars, map_fst, try_split_once, Day, Error, Grid, Metadata, Result};
 
 type Algorithm = [bool; 512];
 
 #[derive(Clone)]
-struct Image {
-    pixels: Vec<Vec<bool>>,
-    size: usize,
+pub struct Image {
+    pixels: Grid<bool>,
     lit: u32,
     default_pixel: bool,
     algorithm: Algorithm,
 }
 
 impl Image {
-    fn new(size: usize, default_pixel: bool, algorithm: Algorithm) -> Image {
+    fn new(width: usize, height: usize, default_pixel: bool, algorithm: Algorithm) -> Image {
         Image {
-            pixels: vec![vec![default_pixel; size]; size],
-            size,
+            pixels: Grid::filled(width, height, default_pixel),
             lit: 0,
             default_pixel,
             algorithm,
         }
     }
 
-    fn from_str(input: &str) -> Image {
-        let (algorithm, image_data) = map_fst(parse_algorithm, input.split_once("\n\n").unwrap());
+    fn from_str(input: &str) -> Result<Image> {
+        let (algorithm, image_data) = map_fst(parse_algorithm, try_split_once(input, "\n\n")?);
 
-        Image {
-            pixels: image_data
-                .lines()
-                .map(|line| line.chars().map(|c| is_lit(c)).collect())
-                .collect(),
-            size: image_data.lines().count(),
+        Ok(Image {
+            pixels: Grid::parse(image_data, parse_pixel, "# or .")?,
             lit: 0, // No need to set this now since it will be reset at when enhancing
             default_pixel: false,
-            algorithm,
-        }
+            algorithm: algorithm?,
+        })
     }
 
     fn get_algorithm_index(&self, x: usize, y: usize) -> usize {
+        // Wrapping operations since the border pixels are outside of the image
+        let (left, right) = (x.wrapping_sub(1), x.wrapping_add(1));
+        let (top, bottom) = (y.wrapping_sub(1), y.wrapping_add(1));
         let binary: String = [
-            (x - 1, y - 1),
-            (x, y - 1),
-            (x + 1, y - 1),
-            (x - 1, y),
+            (left, top),
+            (x, top),
+            (right, top),
+            (left, y),
             (x, y),
-            (x + 1, y),
-            (x - 1, y + 1),
-            (x, y + 1),
-            (x + 1, y + 1),
+            (right, y),
+            (left, bottom),
+            (x, bottom),
+            (right, bottom),
         ]
-        .map(|(x, y)| {
-            *self
-                .pixels
-                .get(y)
-                .and_then(|row| row.get(x))
-                .unwrap_or(&self.default_pixel)
-        })
+        .map(|position| *self.pixels.get(position).unwrap_or(&self.default_pixel))
         .iter()
         .fold(String::new(), |s, b| format!("{}{}", s, *b as u8));
 
@@ -63,18 +54,23 @@ impl Image {
     }
 
     fn insert(&mut self, x: usize, y: usize, pixel: bool) {
-        self.pixels[y][x] = pixel;
+        self.pixels[(x, y)] = pixel;
         self.lit += pixel as u32
     }
 
     fn enhance(&self) -> Image {
-        let mut output = Image::new(self.size + 2, !self.default_pixel, self.algorithm);
+        // The infinite background is either all dark or all lit, which is index 0 or 511.
+        let default_pixel = self.algorithm[if self.default_pixel { 511 } else { 0 }];
+        let mut output = Image::new(
+            self.pixels.width() + 2,
+            self.pixels.height() + 2,
+            default_pixel,
+            self.algorithm,
+        );
 
-        for y in 0..output.size {
-            for x in 0..output.size {
-                let i = self.get_algorithm_index(x - 1, y - 1);
-                output.insert(x, y, self.algorithm[i]);
-            }
+        for (x, y) in output.pixels.positions().collect::<Vec<_>>() {
+            let i = self.get_algorithm_index(x.wrapping_sub(1), y.wrapping_sub(1));
+            output.insert(x, y, self.algorithm[i]);
         }
 
         output
@@ -85,14 +81,27 @@ fn is_lit(c: char) -> bool {
     c == '#'
 }
 
-fn parse_algorithm(input: &str) -> Algorithm {
+fn is_pixel(c: char) -> bool {
+    c == '#' || c == '.'
+}
+
+fn parse_pixel(c: char) -> Option<bool> {
+    Some(is_lit(c)).filter(|_| is_pixel(c))
+}
+
+fn parse_algorithm(input: &str) -> Result<Algorithm> {
     let mut algo = [false; 512];
 
+    check_chars(input, is_pixel, "# or .")?;
+    if input.len() != algo.len() {
+        return Err(Error::parse(input, "512 pixels"));
+    }
+
     for (i, c) in input.chars().enumerate() {
         algo[i] = is_lit(c);
     }
 
-    algo
+    Ok(algo)
 }
 
 /* Solutions */
@@ -102,16 +111,29 @@ fn part01(image: &Image) -> u32 {
 }
 
 fn part02(image: Image) -> u32 {
-    (0..50)
-        .into_iter()
-        .fold(image, |image, _| image.enhance())
-        .lit
+    (0..50).fold(image, |image, _| image.enhance()).lit
 }
 
-pub fn day_20() -> Solution {
-    let input = Image::from_str(&read("./input/day_20.txt"));
-    let timer = std::time::Instant::now();
-    Solution::new(20, part01(&input), part02(input), timer.elapsed())
+pub struct Day20;
+
+impl Day for Day20 {
+    type Input = Image;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(20, "Trench Map")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Image::from_str(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input.clone()).to_string()
+    }
 }
 
 /* Tests */
@@ -120,6 +142,7 @@ pub fn day_20() -> Solution {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read;
 
     #[test]
     fn test_get_algorithm_index() {
@@ -131,19 +154,35 @@ mod tests {
 ##..#
 ..#..
 ..###"#,
-        );
+        )
+        .unwrap();
         assert_eq!(image.get_algorithm_index(2, 2), 34)
     }
 
+    #[test]
+    fn test_enhance_background() {
+        let image = |algorithm: &str| {
+            let input = format!("{}\n\n#", algorithm);
+            Image::from_str(&input).unwrap()
+        };
+        // A dark background stays dark when index 0 is dark.
+        assert!(!image(&".".repeat(512)).enhance().default_pixel);
+
+        // It blinks when index 0 is lit and index 511 is dark.
+        let blinking = image(&format!("#{}.", ".".repeat(510)));
+        assert!(blinking.enhance().default_pixel);
+        assert!(!blinking.enhance().enhance().default_pixel);
+    }
+
     #[test]
     fn test_part01() {
-        let input = Image::from_str(&read("./input/day_20.txt"));
+        let input = Image::from_str(&read("./input/day_20.txt")).unwrap();
         assert_eq!(part01(&input), 5432)
     }
 
     #[test]
     fn test_part02() {
-        let input = Image::from_str(&read("./input/day_20.txt"));
+        let input = Image::from_str(&read("./input/day_20.txt")).unwrap();
         assert_eq!(part02(input), 16016)
     }
 }
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, hash::Hash};
 
-use crate::{read_lines, Solution};
+use crate::{try_parse, try_split_once, Day, Error, Metadata, Result};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 struct Player {
@@ -75,13 +75,24 @@ fn quantum_play(
     (current_win_count, other_win_count)
 }
 
-fn parse_start_positions(input: Vec<String>) -> (u32, u32) {
+fn parse_start_positions<S: AsRef<str>>(input: &[S]) -> Result<(u32, u32)> {
     let start_positions: Vec<u32> = input
-        .into_iter()
-        .map(|line| line[line.len() - 1..].parse().unwrap())
-        .collect();
+        .iter()
+        .map(|line| {
+            let (_, position) = try_split_once(line.as_ref(), ": ")?;
+            match try_parse(position)? {
+                start @ 1..=10 => Ok(start),
+                _ => Err(Error::parse(position, "a position from 1 to 10")),
+            }
+        })
+        .collect::<Result<_>>()?;
+
+    if start_positions.len() != 2 {
+        let last = input.last().map_or("", |line| line.as_ref());
+        return Err(Error::parse(last, "two players"));
+    }
 
-    (start_positions[0], start_positions[1])
+    Ok((start_positions[0], start_positions[1]))
 }
 
 /* Solutions */
@@ -117,10 +128,26 @@ fn part02((start_p1, start_p2): &(u32, u32)) -> usize {
     s1.max(s2)
 }
 
-pub fn day_21() -> Solution {
-    let input = parse_start_positions(read_lines("./input/day_21.txt"));
-    let timer = std::time::Instant::now();
-    Solution::new(21, part01(&input), part02(&input), timer.elapsed())
+pub struct Day21;
+
+impl Day for Day21 {
+    type Input = (u32, u32);
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(21, "Dirac Dice")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_start_positions(&input.lines().collect::<Vec<&str>>())
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 /* Tests */
@@ -129,16 +156,27 @@ pub fn day_21() -> Solution {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::read_lines;
+
+    #[test]
+    fn test_parse_invalid_position() {
+        let input = "Player 1 starting position: 4\nPlayer 2 starting position: 11";
+        let error = Day21.parse(input).unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 29: expected a position from 1 to 10, found \"11\""
+        );
+    }
 
     #[test]
     fn test_part01() {
-        let input = parse_start_positions(read_lines("./input/day_21.txt"));
+        let input = parse_start_positions(&read_lines("./input/day_21.txt")).unwrap();
         assert_eq!(part01(&input), 925605)
     }
 
     #[test]
     fn test_part02() {
-        let input = parse_start_positions(read_lines("./input/day_21.txt"));
+        let input = parse_start_positions(&read_lines("./input/day_21.txt")).unwrap();
         assert_eq!(part02(&input), 486638407378784)
     }
 }
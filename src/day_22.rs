@@ -1,83 +1,98 @@
-use std::{collections::HashSet, ops::RangeInclusive};
+use std::ops::RangeInclusive;
 
-use crate::{map_pair, read, unsafe_parse, Solution};
+use crate::{map_pair, try_parse, try_split_once, Cuboid, CuboidSet, Day, Error, Metadata, Result};
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Coordinate {
-    x: isize,
-    y: isize,
-    z: isize,
+#[derive(Debug, PartialEq)]
+pub struct Step {
+    on: bool,
+    cuboid: Cuboid,
 }
 
-#[derive(Debug, PartialEq)]
-enum Instruction {
-    On(HashSet<Coordinate>),
-    Off(HashSet<Coordinate>),
+fn parse_range(range: &str, axis: &str) -> Result<RangeInclusive<i64>> {
+    let range = range
+        .strip_prefix(axis)
+        .ok_or_else(|| Error::parse(range, axis))?;
+    let (from, to) = map_pair(try_parse, try_split_once(range, "..")?);
+    let (from, to) = (from?, to?);
+
+    if from > to {
+        return Err(Error::parse(range, "an increasing range"));
+    }
+
+    Ok(from..=to)
 }
 
-impl Instruction {
-    fn from_str(input: &str) -> Self {
-        let (instruction, coord_ranges) = input.split_once(" ").unwrap();
-        let ranges: Vec<RangeInclusive<isize>> = coord_ranges
-            .split(",")
-            .into_iter()
-            .map(|range| map_pair(unsafe_parse, range[2..].split_once("..").unwrap()))
-            .map(|(from, to)| from..=to)
-            .collect();
-
-        let mut coordinates = HashSet::new();
-
-        for x in ranges[0].to_owned() {
-            for y in ranges[1].to_owned() {
-                for z in ranges[2].to_owned() {
-                    coordinates.insert(Coordinate { x, y, z });
-                }
-            }
-        }
+fn parse_step(input: &str) -> Result<Step> {
+    let (instruction, coord_ranges) = try_split_once(input, " ")?;
 
-        match instruction {
-            "on" => Self::On(coordinates),
-            "off" => Self::Off(coordinates),
-            _ => unreachable!(),
-        }
+    if instruction != "on" && instruction != "off" {
+        return Err(Error::parse(instruction, "on or off"));
+    }
+    if coord_ranges.split(",").count() != 3 {
+        return Err(Error::parse(coord_ranges, "x, y and z ranges"));
     }
+
+    let mut ranges = coord_ranges
+        .split(",")
+        .zip(["x=", "y=", "z="])
+        .map(|(range, axis)| parse_range(range, axis));
+
+    Ok(Step {
+        on: instruction == "on",
+        cuboid: Cuboid::new([
+            ranges.next().unwrap()?,
+            ranges.next().unwrap()?,
+            ranges.next().unwrap()?,
+        ]),
+    })
 }
 
-fn reboot(instructions: Vec<Instruction>) -> HashSet<Coordinate> {
-    let mut cubes_that_are_on = HashSet::new();
+/// The cubes that are on after the steps.
+fn reboot(steps: &[Step]) -> CuboidSet {
+    let mut cubes = CuboidSet::new();
 
-    for instruction in instructions {
-        match instruction {
-            Instruction::On(on) => cubes_that_are_on.extend(on),
-            Instruction::Off(off) => {
-                cubes_that_are_on = cubes_that_are_on.difference(&off).copied().collect();
-            }
+    for step in steps {
+        if step.on {
+            cubes.insert(step.cuboid);
+        } else {
+            cubes.remove(&step.cuboid);
         }
     }
 
-    cubes_that_are_on
+    cubes
 }
 
 /* Solutions */
 
-fn part01(input: &String) -> usize {
-    let instructions = input
-        .lines()
-        .take_while(|l| l.len() < 40) // Use the length of the line to only get the initialization procedure area. There are 10 in the test data and 20 in the real data therefor I can't use just take.
-        .map(Instruction::from_str)
-        .collect();
-
-    reboot(instructions).len()
+fn part01(steps: &[Step]) -> i64 {
+    let initialization_area = Cuboid::new([-50..=50, -50..=50, -50..=50]);
+    reboot(steps).clip(&initialization_area).volume()
 }
 
-fn part02(_input: &String) -> &str {
-    "NA"
+fn part02(steps: &[Step]) -> i64 {
+    reboot(steps).volume()
 }
 
-pub fn day_22() -> Solution {
-    let input = read("./input/day_22.txt");
-    let timer = std::time::Instant::now();
-    Solution::new(22, part01(&input), part02(&input), timer.elapsed())
+pub struct Day22;
+
+impl Day for Day22 {
+    type Input = Vec<Step>;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(22, "Reactor Reboot")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        input.lines().map(parse_step).collect()
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).to_string()
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        part02(input).to_string()
+    }
 }
 
 /* Tests */
@@ -86,163 +101,65 @@ pub fn day_22() -> Solution {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
-    use std::iter::FromIterator;
+    use crate::read;
+
+    fn parse_steps(input: &str) -> Vec<Step> {
+        input
+            .lines()
+            .map(parse_step)
+            .collect::<Result<_>>()
+            .unwrap()
+    }
 
     #[test]
     fn test_parse_on_instruction() {
-        let instruction = Instruction::from_str("on x=10..12,y=10..12,z=10..12");
-        println!("{:#?}", instruction);
+        let step = parse_step("on x=10..12,y=10..12,z=10..12").unwrap();
+        assert_eq!(
+            step,
+            Step {
+                on: true,
+                cuboid: Cuboid::new([10..=12, 10..=12, 10..=12])
+            }
+        );
+        assert_eq!(step.cuboid.volume(), 27);
+    }
+
+    #[test]
+    fn test_parse_decreasing_range() {
+        let input = "off x=1..2,y=5..3,z=1..2";
+        let error = parse_step(input).unwrap_err().locate(input);
         assert_eq!(
-            instruction,
-            Instruction::On(HashSet::from_iter(vec![
-                Coordinate {
-                    x: 10,
-                    y: 10,
-                    z: 10
-                },
-                Coordinate {
-                    x: 10,
-                    y: 10,
-                    z: 11
-                },
-                Coordinate {
-                    x: 10,
-                    y: 10,
-                    z: 12
-                },
-                Coordinate {
-                    x: 10,
-                    y: 11,
-                    z: 10
-                },
-                Coordinate {
-                    x: 10,
-                    y: 11,
-                    z: 11
-                },
-                Coordinate {
-                    x: 10,
-                    y: 11,
-                    z: 12
-                },
-                Coordinate {
-                    x: 10,
-                    y: 12,
-                    z: 10
-                },
-                Coordinate {
-                    x: 10,
-                    y: 12,
-                    z: 11
-                },
-                Coordinate {
-                    x: 10,
-                    y: 12,
-                    z: 12
-                },
-                Coordinate {
-                    x: 11,
-                    y: 10,
-                    z: 10
-                },
-                Coordinate {
-                    x: 11,
-                    y: 10,
-                    z: 11
-                },
-                Coordinate {
-                    x: 11,
-                    y: 10,
-                    z: 12
-                },
-                Coordinate {
-                    x: 11,
-                    y: 11,
-                    z: 10
-                },
-                Coordinate {
-                    x: 11,
-                    y: 11,
-                    z: 11
-                },
-                Coordinate {
-                    x: 11,
-                    y: 11,
-                    z: 12
-                },
-                Coordinate {
-                    x: 11,
-                    y: 12,
-                    z: 10
-                },
-                Coordinate {
-                    x: 11,
-                    y: 12,
-                    z: 11
-                },
-                Coordinate {
-                    x: 11,
-                    y: 12,
-                    z: 12
-                },
-                Coordinate {
-                    x: 12,
-                    y: 10,
-                    z: 10
-                },
-                Coordinate {
-                    x: 12,
-                    y: 10,
-                    z: 11
-                },
-                Coordinate {
-                    x: 12,
-                    y: 10,
-                    z: 12
-                },
-                Coordinate {
-                    x: 12,
-                    y: 11,
-                    z: 10
-                },
-                Coordinate {
-                    x: 12,
-                    y: 11,
-                    z: 11
-                },
-                Coordinate {
-                    x: 12,
-                    y: 11,
-                    z: 12
-                },
-                Coordinate {
-                    x: 12,
-                    y: 12,
-                    z: 10
-                },
-                Coordinate {
-                    x: 12,
-                    y: 12,
-                    z: 11
-                },
-                Coordinate {
-                    x: 12,
-                    y: 12,
-                    z: 12
-                },
-            ]))
+            error.to_string(),
+            "line 1, column 14: expected an increasing range, found \"5..3\""
         )
     }
 
+    #[test]
+    fn test_reboot_example() {
+        let steps = parse_steps(&read("./input/examples/day_22.txt"));
+        assert_eq!(reboot(&steps[..1]).volume(), 27);
+        assert_eq!(reboot(&steps[..2]).volume(), 27 + 19);
+        assert_eq!(reboot(&steps[..3]).volume(), 27 + 19 - 8);
+        assert_eq!(reboot(&steps).volume(), 39);
+        assert!(reboot(&steps).contains([10, 10, 10]));
+    }
+
+    #[test]
+    fn test_reboot_outside_region() {
+        let steps = parse_steps("on x=-54..-49,y=0..0,z=0..0\non x=60..70,y=0..0,z=0..0");
+        assert_eq!(part01(&steps), 2);
+        assert_eq!(part02(&steps), 6 + 11);
+    }
+
     #[test]
     fn test_part01() {
-        let input = read("./input/day_22.txt");
+        let input = parse_steps(&read("./input/day_22.txt"));
         assert_eq!(part01(&input), 644257)
     }
 
     #[test]
     fn test_part02() {
-        let input = read("./input/day_22.txt");
-        assert_eq!(part02(&input), "NA")
+        let input = parse_steps(&read("./input/day_22.txt"));
+        assert_eq!(part02(&input), 1235484513229032)
     }
 }
@@ -0,0 +1,377 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::{check_chars, Day, Error, Metadata, Result};
+
+const EMPTY: u8 = b'.';
+const HALLWAY_LENGTH: usize = 11;
+// The hallway positions right outside of rooms A to D. Amphipods never stop there.
+const DOORS: [usize; 4] = [2, 4, 6, 8];
+const ENERGY: [usize; 4] = [1, 10, 100, 1000];
+// The rows that were folded away in the diagram, inserted between the rows in part 2.
+const FOLDED_ROWS: [&[u8; 4]; 2] = [b"DCBA", b"DBAC"];
+
+/// The amphipods in the hallway and the side rooms of a burrow.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Burrow {
+    hallway: [u8; HALLWAY_LENGTH],
+    /// The amphipods in each room, from the hallway down.
+    rooms: [Vec<u8>; 4],
+}
+
+fn room_of(amphipod: u8) -> usize {
+    (amphipod - b'A') as usize
+}
+
+fn is_cell(c: char) -> bool {
+    c == EMPTY as char || ('A'..='D').contains(&c)
+}
+
+impl Burrow {
+    /// Parse a diagram of a burrow with rooms of any depth.
+    pub fn new(input: &str) -> Result<Burrow> {
+        parse_burrow(input)
+    }
+
+    /// The number of amphipods that fit in a room.
+    pub fn depth(&self) -> usize {
+        self.rooms[0].len()
+    }
+
+    fn is_organized(&self) -> bool {
+        self.rooms.iter().enumerate().all(|(room, cells)| {
+            cells
+                .iter()
+                .all(|&cell| cell != EMPTY && room_of(cell) == room)
+        })
+    }
+
+    /// Whether the room only contains amphipods that belong there.
+    fn accepts(&self, room: usize) -> bool {
+        self.rooms[room]
+            .iter()
+            .all(|&cell| cell == EMPTY || room_of(cell) == room)
+    }
+
+    /// Whether nothing blocks the hallway between `from` and `to`, not counting `from` itself.
+    fn is_path_clear(&self, from: usize, to: usize) -> bool {
+        let path = if from < to {
+            from + 1..=to
+        } else {
+            to..=from - 1
+        };
+        self.hallway[path].iter().all(|&cell| cell == EMPTY)
+    }
+
+    /// All burrows that can be reached with one move, with the energy the move takes.
+    fn moves(&self) -> Vec<(usize, Burrow)> {
+        // Moving an amphipod into its own room is never a mistake, so take that move when possible.
+        for (position, &amphipod) in self.hallway.iter().enumerate() {
+            if amphipod == EMPTY {
+                continue;
+            }
+            let room = room_of(amphipod);
+            if !self.accepts(room) || !self.is_path_clear(position, DOORS[room]) {
+                continue;
+            }
+            if let Some(depth) = self.rooms[room].iter().rposition(|&cell| cell == EMPTY) {
+                let steps = position.abs_diff(DOORS[room]) + depth + 1;
+                let mut next = self.clone();
+                next.hallway[position] = EMPTY;
+                next.rooms[room][depth] = amphipod;
+                return vec![(steps * ENERGY[room], next)];
+            }
+        }
+
+        let mut moves = vec![];
+
+        // Otherwise move the top amphipod out of a room that still has strangers in it.
+        for room in (0..4).filter(|&room| !self.accepts(room)) {
+            let depth = match self.rooms[room].iter().position(|&cell| cell != EMPTY) {
+                Some(depth) => depth,
+                None => continue,
+            };
+            let amphipod = self.rooms[room][depth];
+
+            for position in (0..HALLWAY_LENGTH).filter(|position| !DOORS.contains(position)) {
+                if !self.is_path_clear(DOORS[room], position) {
+                    continue;
+                }
+                let steps = DOORS[room].abs_diff(position) + depth + 1;
+                let mut next = self.clone();
+                next.rooms[room][depth] = EMPTY;
+                next.hallway[position] = amphipod;
+                moves.push((steps * ENERGY[room_of(amphipod)], next));
+            }
+        }
+
+        moves
+    }
+
+    /// The burrow with the folded rows of part 2 inserted between the first and the last row,
+    /// or `None` if the rooms aren't 2 deep like in the folded diagram.
+    pub fn unfold(&self) -> Option<Burrow> {
+        if self.depth() != 2 {
+            return None;
+        }
+
+        let mut unfolded = self.clone();
+        for (room, cells) in unfolded.rooms.iter_mut().enumerate() {
+            cells.splice(1..1, FOLDED_ROWS.iter().map(|row| row[room]));
+        }
+        Some(unfolded)
+    }
+
+    /// The least energy needed to organize the amphipods, or `None` if they can't be organized.
+    pub fn organize(&self) -> Option<usize> {
+        organize(self.clone())
+    }
+}
+
+fn parse_burrow(input: &str) -> Result<Burrow> {
+    let mut lines = input.lines().skip(1);
+    let hallway_line = lines
+        .next()
+        .ok_or_else(|| Error::parse(input, "a hallway"))?;
+    let cells = hallway_line
+        .strip_prefix('#')
+        .and_then(|line| line.strip_suffix('#'))
+        .filter(|cells| cells.len() == HALLWAY_LENGTH)
+        .ok_or_else(|| Error::parse(hallway_line, "a hallway like #...........#"))?;
+    check_chars(cells, is_cell, "A, B, C, D or .")?;
+
+    // Every cell of the diagram, to point at the amphipod that is one too many.
+    let mut tokens: Vec<&str> = (0..HALLWAY_LENGTH).map(|i| &cells[i..=i]).collect();
+    let mut hallway = [EMPTY; HALLWAY_LENGTH];
+    hallway.copy_from_slice(cells.as_bytes());
+    if let Some(&door) = DOORS.iter().find(|&&door| hallway[door] != EMPTY) {
+        return Err(Error::parse(
+            &cells[door..=door],
+            "no amphipod outside of a room",
+        ));
+    }
+
+    let mut rooms: [Vec<u8>; 4] = Default::default();
+    let mut last_row = hallway_line;
+    for line in lines.take_while(|line| !line.trim().trim_matches('#').is_empty()) {
+        let cells: Vec<&str> = line.trim().trim_matches('#').split('#').collect();
+        if cells.len() != 4 || cells.iter().any(|cell| cell.len() != 1) {
+            return Err(Error::parse(line, "a room row like #A#B#C#D#"));
+        }
+        for (cell, room) in cells.into_iter().zip(rooms.iter_mut()) {
+            check_chars(cell, is_cell, "A, B, C, D or .")?;
+            room.push(cell.as_bytes()[0]);
+            tokens.push(cell);
+        }
+        last_row = line.trim();
+    }
+
+    let depth = rooms[0].len();
+    if depth == 0 {
+        return Err(Error::parse(hallway_line, "rooms below the hallway"));
+    }
+    let mut counts = [0; 4];
+    for token in tokens.into_iter().filter(|&token| token != ".") {
+        let count = &mut counts[room_of(token.as_bytes()[0])];
+        *count += 1;
+        if *count > depth {
+            return Err(Error::parse(
+                token,
+                &format!("at most {} amphipods of each type", depth),
+            ));
+        }
+    }
+    if counts.iter().any(|&count| count < depth) {
+        return Err(Error::parse(
+            last_row,
+            &format!("{} amphipods of each type", depth),
+        ));
+    }
+
+    Ok(Burrow { hallway, rooms })
+}
+
+/// The least energy needed to organize the amphipods, or `None` if they can't be organized.
+fn organize(burrow: Burrow) -> Option<usize> {
+    let mut energies = HashMap::from([(burrow.clone(), 0)]);
+    // Use Reverse to get the lowest instead of the highest energy.
+    let mut heap = BinaryHeap::from(vec![(Reverse(0), burrow)]);
+
+    while let Some((Reverse(energy), burrow)) = heap.pop() {
+        if burrow.is_organized() {
+            return Some(energy);
+        }
+        // Skip burrows that have been reached with less energy since they were pushed.
+        if energies.get(&burrow).is_some_and(|&best| best < energy) {
+            continue;
+        }
+
+        for (cost, next) in burrow.moves() {
+            let energy = energy + cost;
+            if energies.get(&next).is_none_or(|&best| energy < best) {
+                energies.insert(next.clone(), energy);
+                heap.push((Reverse(energy), next));
+            }
+        }
+    }
+
+    None
+}
+
+fn to_answer(energy: Option<usize>) -> String {
+    energy.map_or_else(|| String::from("unsolvable"), |energy| energy.to_string())
+}
+
+/* Solutions */
+
+fn part01(burrow: &Burrow) -> Option<usize> {
+    burrow.organize()
+}
+
+fn part02(burrow: &Burrow) -> Option<usize> {
+    // Checked when parsing.
+    burrow.unfold().unwrap().organize()
+}
+
+pub struct Day23;
+
+impl Day for Day23 {
+    type Input = Burrow;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(23, "Amphipod")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let burrow = parse_burrow(input)?;
+
+        // Part 2 unfolds the diagram, which only works for rooms that are 2 deep.
+        if burrow.depth() != 2 {
+            let rows = input.lines().skip(2).take(burrow.depth());
+            return Err(Error::parse(
+                rows.last().unwrap_or(input).trim(),
+                "rooms of 2 amphipods",
+            ));
+        }
+
+        Ok(burrow)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        to_answer(part01(input))
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        to_answer(part02(input))
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::read;
+
+    fn example() -> Burrow {
+        parse_burrow(&read("./input/examples/day_23.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_parse_burrow() {
+        let burrow = example();
+        assert_eq!(burrow.hallway, [EMPTY; HALLWAY_LENGTH]);
+        assert_eq!(
+            burrow.rooms,
+            [
+                b"BA".to_vec(),
+                b"CD".to_vec(),
+                b"BC".to_vec(),
+                b"DA".to_vec()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_unbalanced_burrow() {
+        let input = "#############\n#...........#\n###B#C#B#D###\n  #A#D#C#B#\n  #########";
+        let error = parse_burrow(input).unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 10: expected at most 2 amphipods of each type, found \"B\""
+        );
+
+        let input = "#############\n#...........#\n###B#C#B#D###\n  #A#D#C#.#\n  #########";
+        let error = parse_burrow(input).unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 3: expected 2 amphipods of each type, found \"#A#D#C#.#\""
+        );
+    }
+
+    #[test]
+    fn test_parse_amphipod_outside_room() {
+        let input = "#############\n#..A........#\n###B#C#B#D###\n  #.#D#C#A#\n  #########";
+        let error = parse_burrow(input).unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: expected no amphipod outside of a room, found \"A\""
+        );
+    }
+
+    #[test]
+    fn test_unfold() {
+        let unfolded = example().unfold().unwrap();
+        assert_eq!(unfolded.rooms[0], b"BDDA".to_vec());
+        assert_eq!(unfolded.rooms[3], b"DACA".to_vec());
+        assert_eq!(unfolded.unfold(), None);
+    }
+
+    #[test]
+    fn test_parse_rooms_of_other_depths() {
+        let input = "#############\n#...........#\n###A#B#C#D###\n  #########";
+        assert_eq!(Burrow::new(input).unwrap().organize(), Some(0));
+        let error = Day23.parse(input).unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected rooms of 2 amphipods, found \"###A#B#C#D###\""
+        );
+    }
+
+    #[test]
+    fn test_organized_burrow() {
+        let input = "#############\n#...........#\n###A#B#C#D###\n  #A#B#C#D#\n  #########";
+        assert_eq!(part01(&parse_burrow(input).unwrap()), Some(0));
+    }
+
+    #[test]
+    fn test_one_move() {
+        let input = "#############\n#.........A.#\n###.#B#C#D###\n  #A#B#C#D#\n  #########";
+        assert_eq!(part01(&parse_burrow(input).unwrap()), Some(8));
+    }
+
+    #[test]
+    fn test_part01_example() {
+        assert_eq!(part01(&example()), Some(12521))
+    }
+
+    #[test]
+    fn test_part02_example() {
+        assert_eq!(part02(&example()), Some(44169))
+    }
+
+    #[test]
+    fn test_part01() {
+        let input = parse_burrow(&read("./input/day_23.txt")).unwrap();
+        assert_eq!(part01(&input), Some(14346))
+    }
+
+    #[test]
+    fn test_part02() {
+        let input = parse_burrow(&read("./input/day_23.txt")).unwrap();
+        assert_eq!(part02(&input), Some(48984))
+    }
+}
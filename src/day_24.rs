@@ -0,0 +1,426 @@
+use std::fmt::{self, Display};
+
+use crate::{try_parse, Day, Error, Metadata, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Register(Register),
+    Number(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand),
+}
+
+fn parse_register(register: &str) -> Result<Register> {
+    match register {
+        "w" => Ok(Register::W),
+        "x" => Ok(Register::X),
+        "y" => Ok(Register::Y),
+        "z" => Ok(Register::Z),
+        _ => Err(Error::parse(register, "w, x, y or z")),
+    }
+}
+
+fn parse_operand(operand: &str) -> Result<Operand> {
+    match parse_register(operand) {
+        Ok(register) => Ok(Operand::Register(register)),
+        Err(_) => try_parse(operand)
+            .map(Operand::Number)
+            .map_err(|_| Error::parse(operand, "a register or a number")),
+    }
+}
+
+fn parse_instruction(line: &str) -> Result<Instruction> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+
+    match words[..] {
+        ["inp", a] => Ok(Instruction::Inp(parse_register(a)?)),
+        [operation, a, b] => {
+            let (a, b) = (parse_register(a)?, parse_operand(b)?);
+            match operation {
+                "add" => Ok(Instruction::Add(a, b)),
+                "mul" => Ok(Instruction::Mul(a, b)),
+                "div" => Ok(Instruction::Div(a, b)),
+                "mod" => Ok(Instruction::Mod(a, b)),
+                "eql" => Ok(Instruction::Eql(a, b)),
+                _ => Err(Error::parse(operation, "add, mul, div, mod or eql")),
+            }
+        }
+        _ => Err(Error::parse(line, "an instruction like inp a or add a b")),
+    }
+}
+
+/// Parse an ALU program with one instruction per line.
+pub fn parse_program(input: &str) -> Result<Vec<Instruction>> {
+    input.lines().map(parse_instruction).collect()
+}
+
+/// The reason an ALU program crashed, with the index of the instruction that crashed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AluError {
+    MissingInput(usize),
+    DivisionByZero(usize),
+    InvalidModulo(usize),
+    Overflow(usize),
+}
+
+impl Display for AluError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AluError::MissingInput(i) => write!(f, "Instruction {} has no input to read", i),
+            AluError::DivisionByZero(i) => write!(f, "Instruction {} divides by zero", i),
+            AluError::InvalidModulo(i) => {
+                write!(f, "Instruction {} takes a negative or zero modulo", i)
+            }
+            AluError::Overflow(i) => write!(f, "Instruction {} overflows", i),
+        }
+    }
+}
+
+impl std::error::Error for AluError {}
+
+/// The arithmetic logic unit of the submarine, with the registers w, x, y and z.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Alu {
+    registers: [i64; 4],
+}
+
+impl Alu {
+    /// An ALU with all registers set to 0.
+    pub fn new() -> Alu {
+        Alu::default()
+    }
+
+    pub fn get(&self, register: Register) -> i64 {
+        self.registers[register as usize]
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(register) => self.get(register),
+            Operand::Number(number) => number,
+        }
+    }
+
+    /// Execute a program, reading the values of the `inp` instructions from the input.
+    pub fn run<I>(&mut self, program: &[Instruction], input: I) -> Result<(), AluError>
+    where
+        I: IntoIterator<Item = i64>,
+    {
+        let mut input = input.into_iter();
+
+        for (i, &instruction) in program.iter().enumerate() {
+            let overflow = AluError::Overflow(i);
+            let (a, value) = match instruction {
+                Instruction::Inp(a) => (a, input.next().ok_or(AluError::MissingInput(i))?),
+                Instruction::Add(a, b) => {
+                    (a, self.get(a).checked_add(self.value(b)).ok_or(overflow)?)
+                }
+                Instruction::Mul(a, b) => {
+                    (a, self.get(a).checked_mul(self.value(b)).ok_or(overflow)?)
+                }
+                Instruction::Div(a, b) => match self.value(b) {
+                    0 => return Err(AluError::DivisionByZero(i)),
+                    b => (a, self.get(a).checked_div(b).ok_or(overflow)?),
+                },
+                Instruction::Mod(a, b) => match (self.get(a), self.value(b)) {
+                    (a, b) if a < 0 || b <= 0 => return Err(AluError::InvalidModulo(i)),
+                    (value, b) => (a, value % b),
+                },
+                Instruction::Eql(a, b) => (a, (self.get(a) == self.value(b)) as i64),
+            };
+            self.registers[a as usize] = value;
+        }
+
+        Ok(())
+    }
+}
+
+const DIGITS: usize = 14;
+
+// The instructions MONAD repeats for every digit. The numbers after the short lines differ per digit.
+const MONAD_BLOCK: [&str; 18] = [
+    "inp w", "mul x 0", "add x z", "mod x 26", "div z", "add x", "eql x w", "eql x 0", "mul y 0",
+    "add y 25", "mul y x", "add y 1", "mul z y", "mul y 0", "add y w", "add y", "mul y x",
+    "add z y",
+];
+const DIVISOR: usize = 4;
+const CHECK: usize = 5;
+const OFFSET: usize = 15;
+
+/// The numbers of one MONAD block. Each block reads a digit `w` and does
+/// `if z % 26 + check != w { z = z / divisor * 26 + w + offset } else { z = z / divisor }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Block {
+    divisor: i64,
+    check: i64,
+    offset: i64,
+}
+
+#[derive(Debug)]
+pub struct Monad {
+    program: Vec<Instruction>,
+    blocks: Vec<Block>,
+}
+
+fn parse_block(lines: &[&str]) -> Result<Block> {
+    let mut numbers = [0; MONAD_BLOCK.len()];
+
+    for (i, (&line, &expected)) in lines.iter().zip(MONAD_BLOCK.iter()).enumerate() {
+        if [DIVISOR, CHECK, OFFSET].contains(&i) {
+            let number = line
+                .strip_prefix(expected)
+                .and_then(|number| number.strip_prefix(' '))
+                .ok_or_else(|| Error::parse(line, &format!("{} and a number", expected)))?;
+            numbers[i] = try_parse(number)?;
+        } else if line != expected {
+            return Err(Error::parse(line, expected));
+        }
+    }
+
+    let block = Block {
+        divisor: numbers[DIVISOR],
+        check: numbers[CHECK],
+        offset: numbers[OFFSET],
+    };
+    if block.divisor != 1 && block.divisor != 26 {
+        return Err(Error::parse(lines[DIVISOR], "div z 1 or div z 26"));
+    }
+    // The search relies on blocks that don't divide z always growing it.
+    if block.divisor == 1 && block.check < 10 {
+        return Err(Error::parse(
+            lines[CHECK],
+            "a number of at least 10 after div z 1",
+        ));
+    }
+
+    Ok(block)
+}
+
+fn parse_monad(input: &str) -> Result<Monad> {
+    let program = parse_program(input)?;
+    let lines: Vec<&str> = input.lines().map(str::trim).collect();
+
+    if lines.len() != DIGITS * MONAD_BLOCK.len() {
+        return Err(Error::parse(
+            input.trim_end(),
+            &format!("{} instructions", DIGITS * MONAD_BLOCK.len()),
+        ));
+    }
+
+    Ok(Monad {
+        program,
+        blocks: lines
+            .chunks(MONAD_BLOCK.len())
+            .map(parse_block)
+            .collect::<Result<_>>()?,
+    })
+}
+
+/// Find the largest or smallest model number that MONAD accepts.
+///
+/// A block that doesn't divide z pushes `w + offset` as a base 26 digit on z. A block that divides z
+/// pops that digit, and only avoids pushing again when `w` equals the popped digit + check.
+/// As there are as many pushes as pops, z can only end at 0 when every pair of digits matches.
+fn search(monad: &Monad, largest: bool) -> Option<u64> {
+    let mut digits = [0; DIGITS];
+    let mut stack = vec![];
+
+    for (j, block) in monad.blocks.iter().enumerate() {
+        if block.divisor == 1 {
+            stack.push(j);
+            continue;
+        }
+        let i = stack.pop()?;
+        // digits[j] must be digits[i] + difference.
+        let difference = monad.blocks[i].offset + block.check;
+        let first = if largest {
+            (9 - difference).min(9)
+        } else {
+            (1 - difference).max(1)
+        };
+        if !(1..=9).contains(&first) || !(1..=9).contains(&(first + difference)) {
+            return None;
+        }
+        digits[i] = first;
+        digits[j] = first + difference;
+    }
+
+    // Double check the model number with the program itself.
+    let mut alu = Alu::new();
+    alu.run(&monad.program, digits).ok()?;
+    if !stack.is_empty() || alu.get(Register::Z) != 0 {
+        return None;
+    }
+
+    Some(
+        digits
+            .iter()
+            .fold(0, |number, &digit| number * 10 + digit as u64),
+    )
+}
+
+fn to_answer(model_number: Option<u64>) -> String {
+    model_number.map_or_else(|| String::from("none"), |number| number.to_string())
+}
+
+/* Solutions */
+
+fn part01(monad: &Monad) -> Option<u64> {
+    search(monad, true)
+}
+
+fn part02(monad: &Monad) -> Option<u64> {
+    search(monad, false)
+}
+
+pub struct Day24;
+
+impl Day for Day24 {
+    type Input = Monad;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(24, "Arithmetic Logic Unit")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_monad(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        to_answer(part01(input))
+    }
+
+    fn part2(&self, input: &Self::Input) -> String {
+        to_answer(part02(input))
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::read;
+
+    fn run(program: &str, input: &[i64]) -> Result<Alu, AluError> {
+        let mut alu = Alu::new();
+        alu.run(&parse_program(program).unwrap(), input.iter().copied())?;
+        Ok(alu)
+    }
+
+    #[test]
+    fn test_parse_program() {
+        assert_eq!(
+            parse_program("inp x\nmul x -1\neql z x").unwrap(),
+            vec![
+                Instruction::Inp(Register::X),
+                Instruction::Mul(Register::X, Operand::Number(-1)),
+                Instruction::Eql(Register::Z, Operand::Register(Register::X)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_program() {
+        let input = "inp w\nsub w 1";
+        let error = parse_program(input).unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected add, mul, div, mod or eql, found \"sub\""
+        );
+        assert!(parse_program("add a 1").is_err());
+        assert!(parse_program("add w one").is_err());
+        assert!(parse_program("inp").is_err());
+    }
+
+    #[test]
+    fn test_negate() {
+        let alu = run("inp x\nmul x -1", &[7]).unwrap();
+        assert_eq!(alu.get(Register::X), -7);
+    }
+
+    #[test]
+    fn test_three_times_larger() {
+        let program = "inp z\ninp x\nmul z 3\neql z x";
+        assert_eq!(run(program, &[2, 6]).unwrap().get(Register::Z), 1);
+        assert_eq!(run(program, &[2, 7]).unwrap().get(Register::Z), 0);
+    }
+
+    #[test]
+    fn test_binary() {
+        let program = read("./input/examples/day_24.txt");
+        let alu = run(&program, &[11]).unwrap();
+        let bits = [Register::W, Register::X, Register::Y, Register::Z].map(|r| alu.get(r));
+        assert_eq!(bits, [1, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_crash() {
+        assert_eq!(run("inp w\ninp x", &[1]), Err(AluError::MissingInput(1)));
+        assert_eq!(run("div w 0", &[]), Err(AluError::DivisionByZero(0)));
+        assert_eq!(
+            run("add w -1\nmod w 2", &[]),
+            Err(AluError::InvalidModulo(1))
+        );
+        assert_eq!(run("div w x", &[]), Err(AluError::DivisionByZero(0)));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let overflow = Err(AluError::Overflow(1));
+        assert_eq!(run("inp w\nadd w 1", &[i64::MAX]), overflow);
+        assert_eq!(run("inp w\nmul w w", &[i64::MAX]), overflow);
+        assert_eq!(run("inp w\ndiv w -1", &[i64::MIN]), overflow);
+        assert_eq!(
+            run("inp w\ndiv w -1", &[i64::MAX])
+                .unwrap()
+                .get(Register::W),
+            -i64::MAX
+        );
+    }
+
+    #[test]
+    fn test_division_truncates() {
+        let alu = run("add w -7\ndiv w 2", &[]).unwrap();
+        assert_eq!(alu.get(Register::W), -3);
+    }
+
+    #[test]
+    fn test_parse_invalid_monad() {
+        let input = read("./input/day_24.txt").replacen("mod x 26", "mod x 25", 1);
+        let error = parse_monad(&input).unwrap_err().locate(&input);
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 1: expected mod x 26, found \"mod x 25\""
+        );
+    }
+
+    #[test]
+    fn test_part01() {
+        let input = parse_monad(&read("./input/day_24.txt")).unwrap();
+        assert_eq!(part01(&input), Some(95984919998191))
+    }
+
+    #[test]
+    fn test_part02() {
+        let input = parse_monad(&read("./input/day_24.txt")).unwrap();
+        assert_eq!(part02(&input), Some(91211619521191))
+    }
+}
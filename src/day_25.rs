@@ -0,0 +1,231 @@
+use std::{
+    fmt::{self, Display},
+    iter,
+};
+
+use crate::{check_chars, Day, Error, Metadata, Result};
+
+const EMPTY: u8 = b'.';
+const EAST: u8 = b'>';
+const SOUTH: u8 = b'v';
+
+/// The sea cucumbers on the sea floor. Cucumbers that move off an edge come back on the opposite edge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Herd {
+    rows: Vec<Vec<u8>>,
+}
+
+impl Herd {
+    pub fn new(input: &str) -> Result<Herd> {
+        let width = input.lines().next().map_or(0, str::len);
+
+        if width == 0 {
+            return Err(Error::parse(input, "a map of the sea floor"));
+        }
+        for line in input.lines() {
+            check_chars(line, |c| ".>v".contains(c), "., > or v")?;
+            if line.len() != width {
+                return Err(Error::parse(line, &format!("{} locations", width)));
+            }
+        }
+
+        Ok(Herd {
+            rows: input.lines().map(|line| line.bytes().collect()).collect(),
+        })
+    }
+
+    /// Move all cucumbers of one herd that can move at the same time. Returns whether any moved.
+    fn move_herd(&mut self, herd: u8) -> bool {
+        let (height, width) = (self.rows.len(), self.rows[0].len());
+        let next = |y: usize, x: usize| match herd {
+            EAST => (y, (x + 1) % width),
+            _ => ((y + 1) % height, x),
+        };
+
+        let moving: Vec<((usize, usize), (usize, usize))> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (y, x)))
+            .filter(|&(y, x)| self.rows[y][x] == herd)
+            .map(|(y, x)| ((y, x), next(y, x)))
+            .filter(|&(_, (y, x))| self.rows[y][x] == EMPTY)
+            .collect();
+
+        for &((from_y, from_x), (to_y, to_x)) in &moving {
+            self.rows[from_y][from_x] = EMPTY;
+            self.rows[to_y][to_x] = herd;
+        }
+
+        !moving.is_empty()
+    }
+
+    /// Move the east facing herd and then the south facing herd. Returns whether any cucumber moved.
+    pub fn step(&mut self) -> bool {
+        let east = self.move_herd(EAST);
+        let south = self.move_herd(SOUTH);
+        east || south
+    }
+
+    /// The state after each step that moved a cucumber, until none of them can move
+    /// or the herds turn out to go round in circles. That can take a few steps past the
+    /// first repeated state.
+    pub fn states(&self) -> impl Iterator<Item = Herd> {
+        let mut herd = self.clone();
+        let mut cycle = Cycle::new(self);
+        iter::from_fn(move || (herd.step() && !cycle.is_repeated(&herd)).then(|| herd.clone()))
+    }
+
+    /// The first step on which no sea cucumber moves, or `None` if the herds keep moving forever.
+    pub fn settle(&self) -> Option<usize> {
+        let mut herd = self.clone();
+        let mut cycle = Cycle::new(self);
+        let mut steps = 1;
+
+        while herd.step() {
+            if cycle.is_repeated(&herd) {
+                return None;
+            }
+            steps += 1;
+        }
+
+        Some(steps)
+    }
+}
+
+/// Brent's cycle detection: the steps only depend on the map, so herds that get back to an
+/// earlier state go round in circles. Only one earlier state is kept, and it is replaced
+/// after 1, 2, 4, 8 and so on steps.
+struct Cycle {
+    saved: Herd,
+    power: usize,
+    length: usize,
+}
+
+impl Cycle {
+    fn new(herd: &Herd) -> Cycle {
+        Cycle {
+            saved: herd.clone(),
+            power: 1,
+            length: 0,
+        }
+    }
+
+    fn is_repeated(&mut self, herd: &Herd) -> bool {
+        if *herd == self.saved {
+            return true;
+        }
+
+        self.length += 1;
+        if self.length == self.power {
+            self.saved = herd.clone();
+            self.power *= 2;
+            self.length = 0;
+        }
+
+        false
+    }
+}
+
+impl Display for Herd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.rows {
+            writeln!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+        Ok(())
+    }
+}
+
+/* Solutions */
+
+fn part01(herd: &Herd) -> Option<usize> {
+    herd.settle()
+}
+
+pub struct Day25;
+
+impl Day for Day25 {
+    type Input = Herd;
+
+    fn metadata(&self) -> Metadata {
+        Metadata::new(25, "Sea Cucumber")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Herd::new(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> String {
+        part01(input).map_or_else(|| String::from("never settles"), |step| step.to_string())
+    }
+
+    // There is no puzzle for part 2, the star is given for completing all other days.
+    fn part2(&self, _input: &Self::Input) -> String {
+        String::from("Merry Christmas")
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::read;
+
+    #[test]
+    fn test_one_row() {
+        let mut herd = Herd::new("...>>>>>...").unwrap();
+        herd.step();
+        assert_eq!(herd.to_string(), "...>>>>.>..\n");
+        herd.step();
+        assert_eq!(herd.to_string(), "...>>>.>.>.\n");
+    }
+
+    #[test]
+    fn test_wrap_around() {
+        let herd =
+            Herd::new("...>...\n.......\n......>\nv.....>\n......>\n.......\n..vvv..").unwrap();
+        let states: Vec<String> = herd.states().take(4).map(|herd| herd.to_string()).collect();
+        assert_eq!(
+            states[0],
+            "..vv>..\n.......\n>......\nv.....>\n>......\n.......\n....v..\n"
+        );
+        assert_eq!(
+            states[3],
+            ">......\n..v....\n..>.v..\n.>.v...\n...>...\n.......\nv......\n"
+        );
+    }
+
+    #[test]
+    fn test_never_settles() {
+        let herd = Herd::new(">.").unwrap();
+        assert_eq!(herd.settle(), None);
+        assert!(herd.states().count() <= 2);
+    }
+
+    #[test]
+    fn test_parse_invalid_herd() {
+        let input = "..>\n.x.";
+        let error = Herd::new(input).unwrap_err().locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: expected ., > or v, found \"x\""
+        );
+        assert!(Herd::new("..>\n..").is_err());
+        assert!(Herd::new("").is_err());
+    }
+
+    #[test]
+    fn test_part01_example() {
+        let herd = Herd::new(&read("./input/examples/day_25.txt")).unwrap();
+        assert_eq!(part01(&herd), Some(58));
+        assert_eq!(
+            herd.states().last().unwrap().to_string(),
+            "..>>v>vv..\n..v.>>vv..\n..>>v>>vv.\n..>>>>>vv.\nv......>vv\nv>v....>>v\nvvv.....>>\n>vv......>\n.>v.vv.v..\n"
+        );
+    }
+
+    #[test]
+    fn test_part01() {
+        let input = Herd::new(&read("./input/day_25.txt")).unwrap();
+        assert_eq!(part01(&input), Some(513))
+    }
+}
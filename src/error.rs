@@ -0,0 +1,132 @@
+use std::{
+    fmt::{self, Display},
+    io,
+};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+pub enum Error {
+    Io { path: String, source: io::Error },
+    Parse(ParseError),
+    UnknownDay(u8),
+}
+
+/// Line and column, both starting at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub position: Option<Position>,
+    pub token: String,
+    pub expected: String,
+    // Address of the token, used to find its position once the complete input is known.
+    address: usize,
+}
+
+impl Error {
+    /// Create an error for a token that didn't match what the parser expected.
+    /// The token should be a slice of the puzzle input to be able to report its position.
+    pub fn parse(token: &str, expected: &str) -> Error {
+        Error::Parse(ParseError {
+            file: None,
+            position: None,
+            token: token.to_string(),
+            expected: expected.to_string(),
+            address: token.as_ptr() as usize,
+        })
+    }
+
+    /// Find the line and column of the offending token in the input it was parsed from.
+    pub fn locate(self, input: &str) -> Error {
+        match self {
+            Error::Parse(mut error) if error.position.is_none() => {
+                let start = input.as_ptr() as usize;
+
+                if (start..=start + input.len()).contains(&error.address) {
+                    let before = &input[..error.address - start];
+                    error.position = Some(Position {
+                        line: before.matches('\n').count() + 1,
+                        column: before.chars().rev().take_while(|&c| c != '\n').count() + 1,
+                    });
+                }
+
+                Error::Parse(error)
+            }
+            error => error,
+        }
+    }
+
+    pub fn in_file(self, path: &str) -> Error {
+        match self {
+            Error::Parse(mut error) => {
+                error.file = Some(path.to_string());
+                Error::Parse(error)
+            }
+            error => error,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "Failed to read {}: {}", path, source),
+            Error::Parse(error) => write!(f, "{}", error),
+            Error::UnknownDay(day) => write!(f, "Day {} is not solved", day),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.file, self.position) {
+            (Some(file), Some(p)) => write!(f, "{}:{}:{}: ", file, p.line, p.column)?,
+            (Some(file), None) => write!(f, "{}: ", file)?,
+            (None, Some(p)) => write!(f, "line {}, column {}: ", p.line, p.column)?,
+            (None, None) => (),
+        }
+
+        write!(f, "expected {}, found {:?}", self.expected, self.token)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_locate_token() {
+        let input = "forward 5\ndown x\nup 3";
+        let error = Error::parse(&input[15..16], "a number")
+            .locate(input)
+            .in_file("input.txt");
+        assert_eq!(
+            error.to_string(),
+            "input.txt:2:6: expected a number, found \"x\""
+        )
+    }
+
+    #[test]
+    fn test_locate_unknown_token() {
+        let error = Error::parse("x", "a number").locate("down 1");
+        assert_eq!(error.to_string(), "expected a number, found \"x\"")
+    }
+}
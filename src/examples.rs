@@ -0,0 +1,231 @@
+use crate::{Check, Solution};
+
+/// The example from a puzzle description and its answers.
+/// An answer is `None` when the description doesn't give one that the day can produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    pub day: u8,
+    pub input: &'static str,
+    pub part_1: Option<&'static str>,
+    pub part_2: Option<&'static str>,
+}
+
+impl Example {
+    const fn new(
+        day: u8,
+        input: &'static str,
+        part_1: Option<&'static str>,
+        part_2: Option<&'static str>,
+    ) -> Example {
+        Example {
+            day,
+            input,
+            part_1,
+            part_2,
+        }
+    }
+
+    pub fn expected(&self, part: u8) -> Option<&'static str> {
+        match part {
+            1 => self.part_1,
+            2 => self.part_2,
+            _ => None,
+        }
+    }
+
+    /// Compare both parts of a solution of the example with the expected answers.
+    pub fn check(&self, solution: &Solution) -> [Check; 2] {
+        [1, 2].map(|part| Check::new(solution, part, self.expected(part)))
+    }
+}
+
+const EXAMPLES: [Example; 24] = [
+    Example::new(
+        1,
+        include_str!("../input/examples/day_01.txt"),
+        Some("7"),
+        Some("5"),
+    ),
+    Example::new(
+        2,
+        include_str!("../input/examples/day_02.txt"),
+        Some("150"),
+        Some("900"),
+    ),
+    Example::new(
+        3,
+        include_str!("../input/examples/day_03.txt"),
+        Some("198"),
+        Some("230"),
+    ),
+    Example::new(
+        4,
+        include_str!("../input/examples/day_04.txt"),
+        Some("4512"),
+        Some("1924"),
+    ),
+    Example::new(
+        5,
+        include_str!("../input/examples/day_05.txt"),
+        Some("5"),
+        Some("12"),
+    ),
+    Example::new(
+        6,
+        include_str!("../input/examples/day_06.txt"),
+        Some("5934"),
+        Some("26984457539"),
+    ),
+    Example::new(
+        7,
+        include_str!("../input/examples/day_07.txt"),
+        Some("37"),
+        Some("168"),
+    ),
+    Example::new(
+        8,
+        include_str!("../input/examples/day_08.txt"),
+        Some("26"),
+        Some("61229"),
+    ),
+    Example::new(
+        9,
+        include_str!("../input/examples/day_09.txt"),
+        Some("15"),
+        Some("1134"),
+    ),
+    Example::new(
+        10,
+        include_str!("../input/examples/day_10.txt"),
+        Some("26397"),
+        Some("288957"),
+    ),
+    Example::new(
+        11,
+        include_str!("../input/examples/day_11.txt"),
+        Some("1656"),
+        Some("195"),
+    ),
+    Example::new(
+        12,
+        include_str!("../input/examples/day_12.txt"),
+        Some("10"),
+        Some("36"),
+    ),
+    // The folded example is a square, which isn't a letter.
+    Example::new(
+        13,
+        include_str!("../input/examples/day_13.txt"),
+        Some("17"),
+        None,
+    ),
+    Example::new(
+        14,
+        include_str!("../input/examples/day_14.txt"),
+        Some("1588"),
+        Some("2188189693529"),
+    ),
+    Example::new(
+        15,
+        include_str!("../input/examples/day_15.txt"),
+        Some("40"),
+        Some("315"),
+    ),
+    Example::new(
+        16,
+        include_str!("../input/examples/day_16.txt"),
+        Some("31"),
+        Some("54"),
+    ),
+    Example::new(
+        17,
+        include_str!("../input/examples/day_17.txt"),
+        Some("45"),
+        Some("112"),
+    ),
+    Example::new(
+        18,
+        include_str!("../input/examples/day_18.txt"),
+        Some("4140"),
+        Some("3993"),
+    ),
+    Example::new(
+        19,
+        include_str!("../input/examples/day_19.txt"),
+        Some("79"),
+        Some("3621"),
+    ),
+    Example::new(
+        20,
+        include_str!("../input/examples/day_20.txt"),
+        Some("35"),
+        Some("3351"),
+    ),
+    Example::new(
+        21,
+        include_str!("../input/examples/day_21.txt"),
+        Some("739785"),
+        Some("444356092776315"),
+    ),
+    // Part 2 has a larger example of its own, this is the small one of part 1.
+    Example::new(
+        22,
+        include_str!("../input/examples/day_22.txt"),
+        Some("39"),
+        None,
+    ),
+    Example::new(
+        23,
+        include_str!("../input/examples/day_23.txt"),
+        Some("12521"),
+        Some("44169"),
+    ),
+    // Part 2 has no puzzle.
+    Example::new(
+        25,
+        include_str!("../input/examples/day_25.txt"),
+        Some("58"),
+        None,
+    ),
+];
+
+/// All examples ordered by day.
+pub fn examples() -> &'static [Example] {
+    &EXAMPLES
+}
+
+pub fn example(day: u8) -> Option<&'static Example> {
+    EXAMPLES.iter().find(|example| example.day == day)
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{Registry, Status};
+
+    #[test]
+    fn test_examples_are_ordered() {
+        assert!(examples().windows(2).all(|w| w[0].day < w[1].day))
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(example(17).unwrap().part_1, Some("45"));
+        assert!(example(24).is_none());
+    }
+
+    #[test]
+    fn test_solve_examples() {
+        let registry = Registry::default();
+
+        for example in examples() {
+            let solution = registry.solve(example.day, example.input).unwrap();
+            for check in example.check(&solution) {
+                assert_ne!(check.status(), Status::Fail, "{}", check);
+            }
+        }
+    }
+}
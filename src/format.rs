@@ -0,0 +1,228 @@
+use std::{str::FromStr, time::Duration};
+
+use crate::{solution::millis, Error, Solution, Timings};
+
+/// How a list of solutions is printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(format: &str) -> Result<Format, Error> {
+        match format {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(Error::parse(format, "table, json, csv or markdown")),
+        }
+    }
+}
+
+impl Format {
+    /// Print the solutions. The wall clock time is only shown in the table.
+    pub fn view(&self, solutions: Vec<Solution>, wall_clock: Duration) {
+        match self {
+            Format::Table => Solution::view_all(solutions, wall_clock),
+            Format::Json => print!("{}", to_json(&solutions)),
+            Format::Csv => print!("{}", to_csv(&solutions)),
+            Format::Markdown => print!("{}", to_markdown(&solutions)),
+        }
+    }
+}
+
+fn timing_values(timings: Timings) -> [f64; 5] {
+    [
+        timings.read,
+        timings.parse,
+        timings.part_1,
+        timings.part_2,
+        timings.total(),
+    ]
+    .map(millis)
+}
+
+const TIMING_NAMES: [&str; 5] = ["read_ms", "parse_ms", "part_1_ms", "part_2_ms", "total_ms"];
+
+/* JSON */
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+pub fn to_json(solutions: &[Solution]) -> String {
+    let objects: Vec<String> = solutions
+        .iter()
+        .map(|solution| {
+            let timings: Vec<String> = TIMING_NAMES
+                .iter()
+                .zip(timing_values(solution.timings()))
+                .map(|(name, value)| format!("\"{}\": {}", name, value))
+                .collect();
+
+            format!(
+                "  {{\"day\": {}, \"part_1\": {}, \"part_2\": {}, {}}}",
+                solution.day(),
+                json_string(solution.part_1()),
+                json_string(solution.part_2()),
+                timings.join(", ")
+            )
+        })
+        .collect();
+
+    if objects.is_empty() {
+        String::from("[]\n")
+    } else {
+        format!("[\n{}\n]\n", objects.join(",\n"))
+    }
+}
+
+/* CSV */
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn to_csv(solutions: &[Solution]) -> String {
+    let mut csv = format!("day,part_1,part_2,{}\n", TIMING_NAMES.join(","));
+
+    for solution in solutions {
+        let timings: Vec<String> = timing_values(solution.timings())
+            .iter()
+            .map(f64::to_string)
+            .collect();
+
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            solution.day(),
+            csv_field(solution.part_1()),
+            csv_field(solution.part_2()),
+            timings.join(",")
+        ));
+    }
+
+    csv
+}
+
+/* Markdown */
+
+fn markdown_row(columns: &[String]) -> String {
+    format!("| {} |\n", columns.join(" | "))
+}
+
+pub fn to_markdown(solutions: &[Solution]) -> String {
+    let mut markdown = String::from(
+        "| Day | Part 1 | Part 2 | Read (ms) | Parse (ms) | Part 1 (ms) | Part 2 (ms) | Total (ms) |\n",
+    );
+    markdown.push_str("|----:|-------:|-------:|----------:|-----------:|------------:|------------:|-----------:|\n");
+
+    for solution in solutions {
+        let mut columns = vec![
+            solution.day().to_string(),
+            solution.part_1().replace('|', "\\|"),
+            solution.part_2().replace('|', "\\|"),
+        ];
+        columns.extend(timing_values(solution.timings()).map(|ms| format!("{:.3}", ms)));
+        markdown.push_str(&markdown_row(&columns));
+    }
+
+    let total: Timings = solutions.iter().map(Solution::timings).sum();
+    let mut columns = vec![String::from("**Total**"), String::new(), String::new()];
+    columns.extend(timing_values(total).map(|ms| format!("{:.3}", ms)));
+    markdown.push_str(&markdown_row(&columns));
+
+    markdown
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    fn solutions() -> Vec<Solution> {
+        let timings = Timings {
+            read: Duration::from_micros(500),
+            parse: Duration::from_millis(1),
+            part_1: Duration::from_millis(2),
+            part_2: Duration::from_millis(3),
+        };
+        vec![
+            Solution::new(13, 675, "HZ\"K", timings),
+            Solution::new(22, "1,2", "NA", timings),
+        ]
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("md".parse::<Format>().unwrap(), Format::Markdown);
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            to_json(&solutions()),
+            r#"[
+  {"day": 13, "part_1": "675", "part_2": "HZ\"K", "read_ms": 0.5, "parse_ms": 1, "part_1_ms": 2, "part_2_ms": 3, "total_ms": 6.5},
+  {"day": 22, "part_1": "1,2", "part_2": "NA", "read_ms": 0.5, "parse_ms": 1, "part_1_ms": 2, "part_2_ms": 3, "total_ms": 6.5}
+]
+"#
+        )
+    }
+
+    #[test]
+    fn test_to_json_empty() {
+        assert_eq!(to_json(&[]), "[]\n")
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(
+            to_csv(&solutions()),
+            r#"day,part_1,part_2,read_ms,parse_ms,part_1_ms,part_2_ms,total_ms
+13,675,"HZ""K",0.5,1,2,3,6.5
+22,"1,2",NA,0.5,1,2,3,6.5
+"#
+        )
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let markdown = to_markdown(&solutions());
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[2],
+            "| 13 | 675 | HZ\"K | 0.500 | 1.000 | 2.000 | 3.000 | 6.500 |"
+        );
+        assert_eq!(
+            lines[4],
+            "| **Total** |  |  | 1.000 | 2.000 | 4.000 | 6.000 | 13.000 |"
+        );
+    }
+}
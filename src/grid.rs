@@ -0,0 +1,280 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use crate::{try_parse_digit_grid, Error, Result};
+
+const ADJACENT: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const ADJACENT_DIAGONAL: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid of cells, indexed by `(x, y)` with `(0, 0)` in the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    // The cells row by row.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from equally long rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows don't have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "All rows of a grid must have the same length"
+        );
+
+        Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Parse a map with one character per cell. `parse_cell` returns `None` for invalid characters.
+    pub fn parse<F>(input: &str, parse_cell: F, expected: &str) -> Result<Grid<T>>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+        let mut rows = vec![];
+
+        for line in input.lines() {
+            let row = line
+                .char_indices()
+                .map(|(i, c)| {
+                    parse_cell(c).ok_or_else(|| Error::parse(&line[i..i + c.len_utf8()], expected))
+                })
+                .collect::<Result<Vec<T>>>()?;
+            if row.len() != width {
+                return Err(Error::parse(line, &format!("{} cells", width)));
+            }
+            rows.push(row);
+        }
+
+        Ok(Grid::from_rows(rows))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The cell at the position, or `None` when the position is outside of the grid.
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// All positions row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All positions with their cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // Chunks of 0 would panic, an empty grid has no rows anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    fn offset(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    /// The positions above, left, right and below the position that are inside of the grid.
+    pub fn neighbours(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        ADJACENT
+            .iter()
+            .filter_map(move |&offset| self.offset(position, offset))
+    }
+
+    /// The neighbours including the diagonal ones.
+    pub fn neighbours_diagonal(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        ADJACENT_DIAGONAL
+            .iter()
+            .filter_map(move |&offset| self.offset(position, offset))
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// A grid with every cell set to the value.
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parse a map of digits like `2199943210`.
+    pub fn parse_digits(input: &str) -> Result<Grid<u8>> {
+        let rows: Vec<&str> = input.lines().collect();
+        Ok(Grid::from_rows(try_parse_digit_grid(&rows)?))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", position))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", position))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    fn example() -> Grid<u8> {
+        Grid::parse_digits("123\n456").unwrap()
+    }
+
+    #[test]
+    fn test_parse_digits() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 0)], 3);
+        assert_eq!(grid[(0, 1)], 4);
+        assert!(Grid::parse_digits("123\n45").is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse("#.\n.#", |c| "#.".find(c).map(|i| i == 0), "# or .").unwrap();
+        assert_eq!(
+            grid,
+            Grid::from_rows(vec![vec![true, false], vec![false, true]])
+        );
+
+        let input = "#.\n.x";
+        let error = Grid::parse(input, |c| "#.".find(c), "# or .")
+            .unwrap_err()
+            .locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: expected # or ., found \"x\""
+        );
+    }
+
+    #[test]
+    fn test_get() {
+        let mut grid = example();
+        assert_eq!(grid.get((1, 1)), Some(&5));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        grid[(1, 1)] = 0;
+        assert_eq!(grid.get((1, 1)), Some(&0));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = example();
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours((1, 1)).count(), 3);
+        assert_eq!(
+            grid.neighbours_diagonal((1, 0)).collect::<Vec<_>>(),
+            vec![(0, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = example();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![[1, 2, 3], [4, 5, 6]]);
+        let columns: Vec<Vec<u8>> = grid
+            .columns()
+            .map(|column| column.copied().collect())
+            .collect();
+        assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(example().to_string(), "123\n456\n");
+        assert_eq!(example().map(|&d| d * 2 % 10).to_string(), "246\n802\n");
+    }
+}
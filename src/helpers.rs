@@ -1,11 +1,18 @@
-pub fn to_columns<T: Copy>(input: &Vec<Vec<T>>) -> Vec<Vec<T>> {
-    let row_length = input.get(0).unwrap().len();
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use crate::{Error, Result};
+
+pub fn to_columns<T: Copy>(input: &[Vec<T>]) -> Vec<Vec<T>> {
+    let row_length = input.first().map_or(0, Vec::len);
     let mut columns = vec![];
 
     for x in 0..row_length {
         let mut column = vec![];
-        for y in 0..input.len() {
-            column.push(input[y][x]);
+        for row in input {
+            column.push(row[x]);
         }
         columns.push(column);
     }
@@ -33,7 +40,7 @@ where
     (f(fst), g(snd))
 }
 
-pub fn parse_number_string<N>(string: &String) -> Vec<N>
+pub fn parse_number_string<N>(string: &str) -> Vec<N>
 where
     N: std::str::FromStr,
 {
@@ -56,6 +63,52 @@ pub fn unsafe_parse<N: std::str::FromStr>(value: &str) -> N {
         .unwrap_or_else(|| panic!("Failed to parse value: {}", value))
 }
 
+pub fn try_parse_number_string<N>(string: &str) -> Result<Vec<N>>
+where
+    N: std::str::FromStr,
+{
+    string
+        .char_indices()
+        .map(|(i, c)| try_parse(&string[i..i + c.len_utf8()]))
+        .collect()
+}
+
+/// Parse rows of single digit numbers where all rows must have the same length.
+pub fn try_parse_digit_grid<S: AsRef<str>>(rows: &[S]) -> Result<Vec<Vec<u8>>> {
+    let width = rows.first().map_or(0, |row| row.as_ref().len());
+
+    rows.iter()
+        .map(|row| {
+            let row = row.as_ref();
+            if row.len() == width {
+                try_parse_number_string(row)
+            } else {
+                Err(Error::parse(row, &format!("{} digits", width)))
+            }
+        })
+        .collect()
+}
+
+pub fn try_parse<N: std::str::FromStr>(value: &str) -> Result<N> {
+    value
+        .parse::<N>()
+        .map_err(|_| Error::parse(value, std::any::type_name::<N>()))
+}
+
+/// Fail at the first character that isn't valid.
+pub fn check_chars<F: Fn(char) -> bool>(value: &str, is_valid: F, expected: &str) -> Result<()> {
+    match value.char_indices().find(|&(_, c)| !is_valid(c)) {
+        Some((i, c)) => Err(Error::parse(&value[i..i + c.len_utf8()], expected)),
+        None => Ok(()),
+    }
+}
+
+pub fn try_split_once<'a>(value: &'a str, separator: &str) -> Result<(&'a str, &'a str)> {
+    value
+        .split_once(separator)
+        .ok_or_else(|| Error::parse(value, &format!("{:?} as separator", separator)))
+}
+
 pub fn sort_desc<T: Ord + Copy>(vec: Vec<T>) -> Vec<T> {
     let mut v = vec.clone();
     v.sort_by_key(|&w| std::cmp::Reverse(w));
@@ -73,3 +126,62 @@ where
 pub fn is_upper(string: &str) -> bool {
     string.to_uppercase() == string
 }
+
+/// Map the items on `threads` threads. The results are in the same order as the items.
+pub fn parallel_map<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.max(1))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = vec![];
+                    // Take the next item until all are taken, so a slow item doesn't hold up the others.
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        match items.get(index) {
+                            Some(item) => results.push((index, f(item))),
+                            None => return results,
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_parallel_map_keeps_order() {
+        let items: Vec<u64> = (0..100).collect();
+        assert_eq!(
+            parallel_map(&items, 4, |n| n * n),
+            items.iter().map(|n| n * n).collect::<Vec<_>>()
+        )
+    }
+
+    #[test]
+    fn test_parallel_map_more_threads_than_items() {
+        assert_eq!(parallel_map(&[1, 2], 8, |n| n + 1), vec![2, 3]);
+        assert_eq!(parallel_map(&[] as &[u8], 0, |n| *n), vec![]);
+    }
+}
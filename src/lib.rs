@@ -1,3 +1,7 @@
+mod answers;
+mod bench;
+mod cuboid;
+mod day;
 mod day_01;
 mod day_02;
 mod day_03;
@@ -20,44 +24,125 @@ mod day_19;
 mod day_20;
 mod day_21;
 mod day_22;
+mod day_23;
+mod day_24;
+mod day_25;
+mod error;
+mod examples;
+mod format;
+mod grid;
 mod helpers;
 mod read_input;
+mod registry;
 mod solution;
 
-pub use day_01::day_01;
-pub use day_02::day_02;
-pub use day_03::day_03;
-pub use day_04::day_04;
-pub use day_05::day_05;
-pub use day_06::day_06;
-pub use day_07::day_07;
-pub use day_08::day_08;
-pub use day_09::day_09;
-pub use day_10::day_10;
-pub use day_11::day_11;
-pub use day_12::day_12;
-pub use day_13::day_13;
-pub use day_14::day_14;
-pub use day_15::day_15;
-pub use day_16::day_16;
-pub use day_17::day_17;
-pub use day_18::day_18;
-pub use day_19::day_19;
-pub use day_20::day_20;
-pub use day_21::day_21;
-pub use day_22::day_22;
+pub use answers::Answers;
+pub use answers::Check;
+pub use answers::Status;
+pub use bench::Baseline;
+pub use bench::Bench;
+pub use bench::Comparison;
+pub use bench::Stats;
+pub use cuboid::Cuboid;
+pub use cuboid::CuboidSet;
+pub use cuboid::Point3;
+pub use day::Day;
+pub use day::Metadata;
+pub use day::Part;
+pub use day::Solver;
+pub use day_01::Day01;
+pub use day_02::Day02;
+pub use day_03::Day03;
+pub use day_04::Day04;
+pub use day_05::Day05;
+pub use day_06::Day06;
+pub use day_07::Day07;
+pub use day_08::Day08;
+pub use day_09::Day09;
+pub use day_10::Day10;
+pub use day_11::Day11;
+pub use day_12::Day12;
+pub use day_13::Day13;
+pub use day_14::Day14;
+pub use day_15::lowest_risk_path;
+pub use day_15::Connectivity;
+pub use day_15::Day15;
+pub use day_15::Heuristic;
+pub use day_15::Pathfinder;
+pub use day_15::RiskPath;
+pub use day_15::Tiling;
+pub use day_15::TilingError;
+pub use day_16::compile;
+pub use day_16::parse_expression;
+pub use day_16::parse_transmission;
+pub use day_16::Contents;
+pub use day_16::Day16;
+pub use day_16::EncodeError;
+pub use day_16::LengthType;
+pub use day_16::Packet;
+pub use day_16::TypeId;
+pub use day_17::Day17;
+pub use day_18::Day18;
+pub use day_19::register_scanners;
+pub use day_19::scanner_rotations;
+pub use day_19::Day19;
+pub use day_19::Registration;
+pub use day_19::Rotation;
+pub use day_19::Scanner;
+pub use day_19::Transform;
+pub use day_19::UnlinkedScanners;
+pub use day_19::SCANNER_OVERLAP;
+pub use day_20::Day20;
+pub use day_21::Day21;
+pub use day_22::Day22;
+pub use day_23::Burrow;
+pub use day_23::Day23;
+pub use day_24::parse_program;
+pub use day_24::Alu;
+pub use day_24::AluError;
+pub use day_24::Day24;
+pub use day_24::Instruction;
+pub use day_24::Operand;
+pub use day_24::Register;
+pub use day_25::Day25;
+pub use day_25::Herd;
+pub use error::Error;
+pub use error::ParseError;
+pub use error::Position;
+pub use error::Result;
+pub use examples::example;
+pub use examples::examples;
+pub use examples::Example;
+pub use format::to_csv;
+pub use format::to_json;
+pub use format::to_markdown;
+pub use format::Format;
+pub use grid::Grid;
+pub use helpers::check_chars;
 pub use helpers::is_upper;
 pub use helpers::map_both;
 pub use helpers::map_fst;
 pub use helpers::map_pair;
 pub use helpers::map_snd;
+pub use helpers::parallel_map;
 pub use helpers::parse_number_string;
 pub use helpers::pipe;
 pub use helpers::sort_desc;
 pub use helpers::to_columns;
+pub use helpers::try_parse;
+pub use helpers::try_parse_digit_grid;
+pub use helpers::try_parse_number_string;
+pub use helpers::try_split_once;
 pub use helpers::unsafe_parse;
 pub use read_input::read;
 pub use read_input::read_comma_separated_number_input;
 pub use read_input::read_lines;
 pub use read_input::read_number_input;
+pub use read_input::read_stdin;
+pub use read_input::to_comma_separated_numbers;
+pub use read_input::to_lines;
+pub use read_input::to_numbers;
+pub use read_input::try_read;
+pub use registry::Registry;
 pub use solution::Solution;
+pub use solution::Timings;
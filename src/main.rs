@@ -1,35 +1,234 @@
-use std::env;
+use std::{
+    env, fs, process, thread,
+    time::{Duration, Instant},
+};
 
 use advent_of_code::{
-    day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09, day_10, day_11, day_12,
-    day_13, day_14, day_15, day_16, day_17, day_18, day_19, day_20, day_21, day_22, Solution,
+    example, parallel_map, read_stdin, try_read, Answers, Baseline, Bench, Comparison, Error, Part,
+    Registry, Result, Solution, Solver, Status,
 };
 
+mod args;
+
+use args::{parse_args, Args, Command, USAGE};
+
 fn main() {
-    let days = [
-        day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09, day_10, day_11,
-        day_12, day_13, day_14, day_15, day_16, day_17, day_18, day_19, day_20, day_21, day_22,
-    ];
+    let registry = Registry::default();
+    let Args {
+        days,
+        part,
+        input,
+        format,
+        check: answers,
+        bench: runs,
+        baseline,
+        save_baseline,
+        parallel,
+        example,
+    } = match parse_args(env::args().skip(1)) {
+        Ok(Command::Run(args)) => args,
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if example {
+        solve_examples(&registry, &days, part);
+        return;
+    }
+
+    let result = selected(&registry, &days, input).and_then(|days| match (runs, answers) {
+        (Some(runs), _) => bench(&days, runs, part, baseline, save_baseline),
+        (None, Some(path)) => solve_all(&days, part, parallel)
+            .and_then(|(solutions, _)| check(&solutions, part, &path)),
+        (None, None) => solve_all(&days, part, parallel)
+            .map(|(solutions, wall_clock)| format.view(solutions, wall_clock)),
+    });
+
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
 
-    match parse_arg_day() {
-        Some(day) => Solution::view(solve_day(&days, day)),
-        None => Solution::view_all(days.map(solve).to_vec()),
+/// The selected days, or all days if none are selected, together with the path of their input.
+fn selected<'a>(
+    registry: &'a Registry,
+    days: &[u8],
+    input: Option<String>,
+) -> Result<Vec<(&'a dyn Solver, String)>> {
+    let solvers = if days.is_empty() {
+        registry.iter().collect()
+    } else {
+        days.iter()
+            .map(|&day| registry.get(day).ok_or(Error::UnknownDay(day)))
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    // The arguments only allow an input together with a single day.
+    Ok(solvers
+        .into_iter()
+        .map(|solver| {
+            let path = input.clone();
+            (
+                solver,
+                path.unwrap_or_else(|| solver.metadata().input_path()),
+            )
+        })
+        .collect())
+}
+
+/// Solve the days in order, or on one thread per core when `parallel` is set.
+/// Returns the solutions ordered by day and the wall clock time it took.
+fn solve_all(
+    days: &[(&dyn Solver, String)],
+    part: Option<Part>,
+    parallel: bool,
+) -> Result<(Vec<Solution>, Duration)> {
+    let timer = Instant::now();
+    let solve = |(day, path): &(&dyn Solver, String)| solve_input(*day, path, part);
+
+    let solutions: Result<Vec<Solution>> = if parallel {
+        let threads = thread::available_parallelism().map_or(1, usize::from);
+        parallel_map(days, threads, solve).into_iter().collect()
+    } else {
+        days.iter().map(solve).collect()
+    };
+
+    solutions.map(|solutions| (solutions, timer.elapsed()))
+}
+
+/// Read a file, or stdin when the path is -. Returns the input and the name to use in errors.
+fn read_input(path: &str) -> Result<(String, &str)> {
+    if path == "-" {
+        Ok((read_stdin()?, "stdin"))
+    } else {
+        Ok((try_read(path)?, path))
     }
 }
 
-fn parse_arg_day() -> Option<usize> {
-    env::args()
-        .collect::<Vec<String>>()
-        .get(1)
-        .map(|day| day.parse::<usize>().unwrap() - 1)
+fn solve_input(day: &dyn Solver, path: &str, part: Option<Part>) -> Result<Solution> {
+    let timer = Instant::now();
+    let (input, name) = read_input(path)?;
+    let read = timer.elapsed();
+
+    day.solve_part(&input, part)
+        .map(|solution| solution.with_read_time(read))
+        .map_err(|error| error.in_file(name))
+}
+
+/// Compare the solutions with the answers file and exit with status 1 if any part is wrong.
+fn check(solutions: &[Solution], part: Option<Part>, path: &str) -> Result<()> {
+    let content = try_read(path)?;
+    let answers: Answers = content
+        .parse()
+        .map_err(|error: Error| error.locate(&content).in_file(path))?;
+
+    let checks: Vec<_> = solutions
+        .iter()
+        .flat_map(|s| answers.check(s))
+        .filter(|check| part.is_none_or(|part| part.number() == check.part))
+        .collect();
+    checks.iter().for_each(|check| println!("{}", check));
+
+    if checks.iter().any(|check| check.status() == Status::Fail) {
+        process::exit(1);
+    }
+
+    Ok(())
 }
 
-fn solve_day<F: Fn() -> Solution>(days: &[F], day: usize) -> Solution {
-    days.get(day)
-        .map(solve)
-        .unwrap_or_else(|| panic!("You need to provide a day between 1 - {}", days.len()))
+/// Benchmark the days and compare with, or save, a baseline.
+/// Exits with status 1 if any phase is slower than the baseline allows.
+fn bench(
+    days: &[(&dyn Solver, String)],
+    runs: usize,
+    part: Option<Part>,
+    baseline: Option<String>,
+    save_baseline: Option<String>,
+) -> Result<()> {
+    let benches = days
+        .iter()
+        .map(|(day, path)| {
+            let (input, name) = read_input(path)?;
+            Bench::run(*day, &input, runs, part).map_err(|error| error.in_file(name))
+        })
+        .collect::<Result<Vec<Bench>>>()?;
+
+    Bench::view_all(&benches);
+
+    if let Some(path) = save_baseline {
+        fs::write(&path, Baseline::new(&benches).to_string())
+            .map_err(|source| Error::Io { path, source })?;
+    }
+
+    if let Some(path) = baseline {
+        let content = try_read(&path)?;
+        let baseline: Baseline = content
+            .parse()
+            .map_err(|error: Error| error.locate(&content).in_file(&path))?;
+
+        let comparisons: Vec<_> = benches.iter().flat_map(|b| b.compare(&baseline)).collect();
+        comparisons
+            .iter()
+            .for_each(|comparison| println!("{}", comparison));
+
+        if comparisons.iter().any(Comparison::is_regression) {
+            process::exit(1);
+        }
+    }
+
+    Ok(())
 }
 
-fn solve<F: Fn() -> Solution>(solve: F) -> Solution {
-    solve()
+/// Solve the examples of the selected days, or all days, and compare with their answers.
+/// Exits with status 1 if any part is wrong or can't be solved.
+fn solve_examples(registry: &Registry, days: &[u8], part: Option<Part>) {
+    let days: Vec<u8> = if days.is_empty() {
+        registry
+            .iter()
+            .map(|solver| solver.metadata().day)
+            .collect()
+    } else {
+        days.to_vec()
+    };
+    let mut failed = false;
+
+    for day in days {
+        let example = match example(day) {
+            Some(example) => example,
+            None => {
+                println!("Day {: >2}: MISSING no example", day);
+                continue;
+            }
+        };
+
+        match registry
+            .get(day)
+            .ok_or(Error::UnknownDay(day))
+            .and_then(|solver| solver.solve_part(example.input, part))
+        {
+            Ok(solution) => {
+                for check in example.check(&solution) {
+                    if part.is_none_or(|part| part.number() == check.part) {
+                        failed |= check.status() == Status::Fail;
+                        println!("{}", check);
+                    }
+                }
+            }
+            Err(error) => {
+                failed = true;
+                println!("Day {: >2}: FAIL {}", day, error);
+            }
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
 }
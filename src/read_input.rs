@@ -1,13 +1,31 @@
-use std::str::FromStr;
+use std::{io::Read, str::FromStr};
 
-use crate::unsafe_parse;
+use crate::{try_parse, unsafe_parse, Error, Result};
 
 pub fn read(path: &str) -> String {
-    std::fs::read_to_string(&path).unwrap()
+    try_read(path).unwrap_or_else(|error| panic!("{}", error))
+}
+
+pub fn try_read(path: &str) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_string(),
+        source,
+    })
+}
+
+pub fn read_stdin() -> Result<String> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|source| Error::Io {
+            path: String::from("stdin"),
+            source,
+        })?;
+    Ok(input)
 }
 
 pub fn read_lines(path: &str) -> Vec<String> {
-    read(path).lines().map(String::from).collect()
+    to_lines(&read(path))
 }
 
 pub fn read_number_input<T: FromStr>(path: &str) -> Vec<T> {
@@ -15,5 +33,17 @@ pub fn read_number_input<T: FromStr>(path: &str) -> Vec<T> {
 }
 
 pub fn read_comma_separated_number_input<T: FromStr>(path: &str) -> Vec<T> {
-    read(path).split(",").map(unsafe_parse).collect()
+    read(path).split(',').map(unsafe_parse).collect()
+}
+
+pub fn to_lines(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+
+pub fn to_numbers<T: FromStr>(input: &str) -> Result<Vec<T>> {
+    input.lines().map(try_parse).collect()
+}
+
+pub fn to_comma_separated_numbers<T: FromStr>(input: &str) -> Result<Vec<T>> {
+    input.split(',').map(try_parse).collect()
 }
@@ -0,0 +1,177 @@
+use crate::{
+    Day, Day01, Day02, Day03, Day04, Day05, Day06, Day07, Day08, Day09, Day10, Day11, Day12, Day13,
+    Day14, Day15, Day16, Day17, Day18, Day19, Day20, Day21, Day22, Day23, Day24, Day25, Error,
+    Result, Solution, Solver,
+};
+
+/// Collection of days ordered by day number.
+pub struct Registry {
+    days: Vec<Box<dyn Solver>>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry { days: vec![] }
+    }
+
+    /// Add a day. A day with the same number as an already registered day will replace it.
+    pub fn register<D: Day + 'static>(&mut self, day: D) -> &mut Registry {
+        let number = Day::metadata(&day).day;
+
+        match self.position(number) {
+            Ok(index) => self.days[index] = Box::new(day),
+            Err(index) => self.days.insert(index, Box::new(day)),
+        }
+
+        self
+    }
+
+    pub fn get(&self, day: u8) -> Option<&dyn Solver> {
+        self.position(day)
+            .ok()
+            .map(|index| self.days[index].as_ref())
+    }
+
+    /// Solve a day with the given puzzle input instead of the input file.
+    pub fn solve(&self, day: u8, input: &str) -> Result<Solution> {
+        self.get(day).ok_or(Error::UnknownDay(day))?.solve(input)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Solver> {
+        self.days.iter().map(|day| day.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.days.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty()
+    }
+
+    fn position(&self, day: u8) -> Result<usize, usize> {
+        self.days.binary_search_by_key(&day, |d| d.metadata().day)
+    }
+}
+
+impl Default for Registry {
+    /// All days solved in this crate.
+    fn default() -> Registry {
+        let mut registry = Registry::new();
+        registry
+            .register(Day01)
+            .register(Day02)
+            .register(Day03)
+            .register(Day04)
+            .register(Day05)
+            .register(Day06)
+            .register(Day07)
+            .register(Day08)
+            .register(Day09)
+            .register(Day10)
+            .register(Day11)
+            .register(Day12)
+            .register(Day13)
+            .register(Day14)
+            .register(Day15)
+            .register(Day16)
+            .register(Day17)
+            .register(Day18)
+            .register(Day19)
+            .register(Day20)
+            .register(Day21)
+            .register(Day22)
+            .register(Day23)
+            .register(Day24)
+            .register(Day25);
+        registry
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{day::TestDay, Part};
+
+    #[test]
+    fn test_default_is_ordered() {
+        let days: Vec<u8> = Registry::default()
+            .iter()
+            .map(|d| d.metadata().day)
+            .collect();
+        assert_eq!(days, (1..=25).collect::<Vec<u8>>())
+    }
+
+    #[test]
+    fn test_register_keeps_order() {
+        let mut registry = Registry::new();
+        registry.register(TestDay(3)).register(TestDay(1));
+        let days: Vec<u8> = registry.iter().map(|d| d.metadata().day).collect();
+        assert_eq!(days, vec![1, 3])
+    }
+
+    #[test]
+    fn test_register_replaces_day() {
+        let mut registry = Registry::default();
+        registry.register(TestDay(1));
+        assert_eq!(registry.len(), 25);
+        assert_eq!(registry.get(1).unwrap().metadata().title, "Test")
+    }
+
+    #[test]
+    fn test_solve_with_input() {
+        let solution = Registry::default().solve(1, "1\n2\n1\n3\n4\n").unwrap();
+        assert_eq!(solution.part_1(), "3");
+        assert_eq!(solution.part_2(), "2");
+    }
+
+    #[test]
+    fn test_solve_malformed_input() {
+        let error = Registry::default()
+            .solve(2, "forward 5\nback 2")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected forward, down or up, found \"back\""
+        )
+    }
+
+    #[test]
+    fn test_solve_unknown_day() {
+        assert!(matches!(
+            Registry::default().solve(30, ""),
+            Err(Error::UnknownDay(30))
+        ))
+    }
+
+    #[test]
+    fn test_get_missing_day() {
+        assert!(Registry::default().get(0).is_none())
+    }
+
+    #[test]
+    fn test_solve_registered_day() {
+        let mut registry = Registry::new();
+        registry.register(TestDay(30));
+        let solution = registry.solve(30, "3").unwrap();
+        assert_eq!(solution.part_1(), "3");
+        assert_eq!(solution.part_2(), "6");
+    }
+
+    #[test]
+    fn test_solve_one_part() {
+        let mut registry = Registry::new();
+        registry.register(TestDay(30));
+        let solution = registry
+            .get(30)
+            .unwrap()
+            .solve_part("3", Some(Part::Two))
+            .unwrap();
+        assert_eq!(solution.part_1(), "-");
+        assert_eq!(solution.part_2(), "6");
+        assert_eq!(solution.timings().part_1, Default::default());
+    }
+}
@@ -1,59 +1,148 @@
 use std::{
     fmt::{self, Display},
+    iter::Sum,
+    ops::Add,
     time::Duration,
 };
 
-#[derive(Clone)]
+/// Time spent in each phase of solving a day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub read: Duration,
+    pub parse: Duration,
+    pub part_1: Duration,
+    pub part_2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.read + self.parse + self.part_1 + self.part_2
+    }
+}
+
+/// The duration in milliseconds, as shown in all outputs.
+pub(crate) fn millis(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1_000_000.0
+}
+
+impl Add for Timings {
+    type Output = Timings;
+
+    fn add(self, other: Timings) -> Timings {
+        Timings {
+            read: self.read + other.read,
+            parse: self.parse + other.parse,
+            part_1: self.part_1 + other.part_1,
+            part_2: self.part_2 + other.part_2,
+        }
+    }
+}
+
+impl Sum for Timings {
+    fn sum<I: Iterator<Item = Timings>>(iter: I) -> Timings {
+        iter.fold(Timings::default(), Timings::add)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Solution {
-    day: String,
+    day: u8,
     part_1: String,
     part_2: String,
-    duration: Duration,
+    timings: Timings,
 }
 
 impl Solution {
-    pub fn new<A, B>(day: u8, part_1: A, part_2: B, duration: Duration) -> Solution
+    pub fn new<A, B>(day: u8, part_1: A, part_2: B, timings: Timings) -> Solution
     where
         A: ToString,
         B: ToString,
     {
         Solution {
-            day: day.to_string(),
+            day,
             part_1: part_1.to_string(),
             part_2: part_2.to_string(),
-            duration,
+            timings,
         }
     }
 
-    pub fn view_all(solutions: Vec<Solution>) {
-        println!(".------------------------------------------------------.",);
-        println!("|              🎄 Advent of Code - 2021 🎄             |",);
-        println!("├-----┬------------┬-----------------┬-----------------┤",);
+    /// Add the time it took to read the input, which happens before the day gets the input.
+    pub fn with_read_time(mut self, read: Duration) -> Solution {
+        self.timings.read = read;
+        self
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub fn part_1(&self) -> &str {
+        &self.part_1
+    }
+
+    pub fn part_2(&self) -> &str {
+        &self.part_2
+    }
+
+    pub fn timings(&self) -> Timings {
+        self.timings
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.timings.total()
+    }
+
+    /// Show the solutions in a table. The footer has both the summed time of all days
+    /// and the wall clock time, which is lower when the days were solved in parallel.
+    pub fn view_all(solutions: Vec<Solution>, wall_clock: Duration) {
+        println!(".--------------------------------------------------------------------------------------------.",);
+        println!("|                                  🎄 Advent of Code - 2021 🎄                                 |",);
+        println!("├-----┬------------┬-----------------┬----------┬----------┬----------┬----------┬-----------┤",);
         println!(
-            "| {: >3} | {: >10} | {: >15} | {: >15} |",
-            "Day", "Part 1", "Part 2", "Time (ms)"
+            "| {: >3} | {: >10} | {: >15} | {: >8} | {: >8} | {: >8} | {: >8} | {: >9} |",
+            "Day", "Part 1", "Part 2", "Read ms", "Parse ms", "P1 ms", "P2 ms", "Total ms"
         );
-        println!("├-----┼------------┼-----------------┼-----------------┤",);
+        println!("├-----┼------------┼-----------------┼----------┼----------┼----------┼----------┼-----------┤",);
         solutions.iter().for_each(Solution::print);
-        println!("|-----┴------------┴-----------------┴-----------------|",);
-        println!("| Total: {: >42} ms |", Solution::total_time(solutions));
-        println!("'------------------------------------------------------'",);
+        println!("|-----┴------------┴-----------------┼----------┼----------┼----------┼----------┼-----------|",);
+        println!(
+            "| CPU time: {: >24} |{}",
+            "",
+            Solution::total_time(&solutions)
+        );
+        println!("| Wall clock: {: >66} | {: >9.3} |", "", millis(wall_clock));
+        println!("'--------------------------------------------------------------------------------------------'",);
     }
 
     pub fn view(solution: Solution) {
-        Solution::view_all(vec![solution]);
+        let duration = solution.duration();
+        Solution::view_all(vec![solution], duration);
     }
 
     fn print(solution: &Solution) {
         println!("{}", solution);
     }
 
-    fn time_to_millis(nanos: u128) -> f64 {
-        nanos as f64 / 1_000_000.0
+    fn total_time(solutions: &[Solution]) -> TimingColumns {
+        TimingColumns(solutions.iter().map(Solution::timings).sum())
     }
+}
 
-    fn total_time(solutions: Vec<Solution>) -> f64 {
-        Solution::time_to_millis(solutions.into_iter().map(|s| s.duration.as_nanos()).sum())
+/// The time columns of the table.
+struct TimingColumns(Timings);
+
+impl Display for TimingColumns {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let TimingColumns(timings) = self;
+        write!(
+            f,
+            " {: >8.3} | {: >8.3} | {: >8.3} | {: >8.3} | {: >9.3} |",
+            millis(timings.read),
+            millis(timings.parse),
+            millis(timings.part_1),
+            millis(timings.part_2),
+            millis(timings.total()),
+        )
     }
 }
 
@@ -61,11 +150,47 @@ impl Display for Solution {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "|  {: >2} | {: >10} | {: >15} | {: >15} |",
+            "|  {: >2} | {: >10} | {: >15} |{}",
             self.day,
             self.part_1,
             self.part_2,
-            Solution::time_to_millis(self.duration.as_nanos()),
+            TimingColumns(self.timings),
+        )
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    fn timings(millis: u64) -> Timings {
+        Timings {
+            read: Duration::from_millis(millis),
+            parse: Duration::from_millis(millis * 2),
+            part_1: Duration::from_millis(millis * 3),
+            part_2: Duration::from_millis(millis * 4),
+        }
+    }
+
+    #[test]
+    fn test_total_timings() {
+        assert_eq!(timings(1).total(), Duration::from_millis(10))
+    }
+
+    #[test]
+    fn test_sum_timings() {
+        assert_eq!(
+            vec![timings(1), timings(2)].into_iter().sum::<Timings>(),
+            timings(3)
         )
     }
+
+    #[test]
+    fn test_with_read_time() {
+        let solution = Solution::new(1, 2, 3, timings(1)).with_read_time(Duration::from_millis(5));
+        assert_eq!(solution.duration(), Duration::from_millis(14))
+    }
 }